@@ -1,8 +1,10 @@
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	SystemConfig, TemplateModuleConfig, DAYS, WASM_BINARY,
 };
+use sc_chain_spec::{ChainSpecExtension, GetExtension};
 use sc_service::ChainType;
+use serde::{Deserialize, Serialize};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::{sr25519, Pair, Public};
@@ -11,8 +13,34 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// The defaults of the market module of a testnet, which the genesis is built from.
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketPresets {
+	/// The accounts, which each create a demo market at genesis and act as its oracle.
+	pub demo_oracles: Vec<AccountId>,
+	/// The outcome amount of the demo markets.
+	pub demo_outcomes: u8,
+	/// The names of the initial market categories.
+	pub categories: Vec<String>,
+}
+
+/// The node specific extensions of the chain spec.
+#[derive(Default, Clone, Debug, Serialize, Deserialize, ChainSpecExtension)]
+#[serde(rename_all = "camelCase")]
+pub struct Extensions {
+	pub market_presets: MarketPresets,
+}
+
+impl Extensions {
+	/// Try to get the extension from the given `ChainSpec` or its extensions.
+	pub fn try_get(extensions: &dyn GetExtension) -> Option<&Self> {
+		sc_chain_spec::get_extension(extensions)
+	}
+}
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -38,6 +66,14 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let extensions = Extensions {
+		market_presets: MarketPresets {
+			demo_oracles: vec![get_account_id_from_seed::<sr25519::Public>("Bob")],
+			demo_outcomes: 2,
+			categories: vec!["Politics".into(), "Sports".into(), "Crypto".into()],
+		},
+	};
+	let genesis_extensions = extensions.clone();
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -60,6 +96,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
 				true,
+				&genesis_extensions,
 			)
 		},
 		// Bootnodes
@@ -72,12 +109,19 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Properties
 		None,
 		// Extensions
-		None,
+		extensions,
 	))
 }
 
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let extensions = Extensions {
+		market_presets: MarketPresets {
+			categories: vec!["Politics".into(), "Sports".into(), "Crypto".into()],
+			..Default::default()
+		},
+	};
+	let genesis_extensions = extensions.clone();
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -108,6 +152,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
 				true,
+				&genesis_extensions,
 			)
 		},
 		// Bootnodes
@@ -120,7 +165,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		None,
 		None,
 		// Extensions
-		None,
+		extensions,
 	))
 }
 
@@ -131,7 +176,12 @@ fn testnet_genesis(
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
+	extensions: &dyn GetExtension,
 ) -> GenesisConfig {
+	let market_presets = Extensions::try_get(extensions)
+		.map(|extensions| extensions.market_presets.clone())
+		.unwrap_or_default();
+
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -152,5 +202,18 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		template_module: TemplateModuleConfig {
+			// The demo markets end one day after genesis.
+			demo_markets: market_presets
+				.demo_oracles
+				.iter()
+				.map(|oracle| (oracle.clone(), market_presets.demo_outcomes, DAYS))
+				.collect(),
+			categories: market_presets
+				.categories
+				.into_iter()
+				.map(|category| category.into_bytes())
+				.collect(),
+		},
 	}
 }
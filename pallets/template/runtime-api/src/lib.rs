@@ -0,0 +1,67 @@
+//! Runtime API definition for the prediction markets of pallet-template.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_template::{
+	BidError, BuyQuote, CreateMarketError, MarketConstants, MarketMetrics, MarketParams,
+	PositionInfo, SettlementEntry,
+};
+use sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait MarketsApi<AccountId, Balance, BlockNumber, MarketId>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		MarketId: Codec,
+	{
+		/// The escrow account which holds the funds of the market.
+		fn market_account(market_id: MarketId) -> AccountId;
+
+		/// The markets which close in the inclusive block range `from..=to`, ordered by the
+		/// close block.
+		fn markets_closing_in(
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, Vec<MarketId>)>;
+
+		/// Performs all checks of `create_market` for the given parameters without creating the
+		/// market and returns the id the market would get.
+		fn validate_market_params(
+			params: MarketParams<AccountId, BlockNumber>,
+		) -> Result<MarketId, CreateMarketError>;
+
+		/// The in and out flows of each participant of a resolved market.
+		fn settlement_report(market_id: MarketId) -> Option<Vec<SettlementEntry<AccountId, Balance>>>;
+
+		/// The economically relevant parameters of the markets, like bonds and periods.
+		fn market_constants() -> MarketConstants<Balance, BlockNumber>;
+
+		/// The outcome positions of the account in all unsettled markets with the escrowed
+		/// amounts and the winnings claimable on redemption.
+		fn account_positions(account: AccountId) -> Vec<PositionInfo<Balance>>;
+
+		/// The time weighted average of the implied probability of the outcome over the last
+		/// `window` blocks.
+		fn twap(market_id: MarketId, outcome_index: u8, window: BlockNumber) -> Option<Perbill>;
+
+		/// The markets tagged with the ISO 3166-1 alpha-2 region, optionally filtered by the
+		/// ISO 639-1 language.
+		fn markets_in_region(region: [u8; 2], language: Option<[u8; 2]>) -> Vec<MarketId>;
+
+		/// Performs the market checks of `buy_outcome` and returns the effect of the bid without
+		/// changing the state.
+		fn quote_buy(
+			market_id: MarketId,
+			outcome_index: u8,
+			buyer: AccountId,
+			price: Balance,
+		) -> Result<BuyQuote<AccountId, Balance>, BidError>;
+
+		/// The health metrics of the markets, like stuck markets and the locked funds.
+		fn market_metrics() -> MarketMetrics<Balance>;
+	}
+}
@@ -4,31 +4,873 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Template;
+use alloc::{vec, vec::Vec};
 use frame_benchmarking::v2::*;
+use frame_support::{
+	sp_runtime::{
+		traits::{Saturating, Zero},
+		Percent, SaturatedConversion,
+	},
+	traits::{Currency, EnsureOrigin, Get, Hooks, NamedReservableCurrency, ReservableCurrency},
+};
 use frame_system::RawOrigin;
 
+fn fund_account<T: Config>(who: &T::AccountId) {
+	let amount = T::CreatorBond::get()
+		.saturating_add(T::OracleBond::get())
+		.saturating_add(T::CreationFee::get())
+		.saturating_add(T::Currency::minimum_balance())
+		.saturating_mul(1_000u32.into());
+	T::Currency::make_free_balance_be(who, amount);
+}
+
+fn outcome_data<T: Config>(outcome_amount: u8) -> OutcomeDataOf<T> {
+	let outcome_data = (0..outcome_amount).map(|i| [i; 32]).collect::<Vec<_>>();
+	OutcomeDataOf::<T>::truncate_from(outcome_data)
+}
+
+fn create_market_with_outcomes<T: Config>(
+	creator: T::AccountId,
+	outcome_amount: u8,
+) -> Result<MarketId, BenchmarkError> {
+	let end = frame_system::Pallet::<T>::block_number().saturating_add(T::MinMarketPeriod::get());
+	create_market_closing_at::<T>(creator, outcome_amount, end)
+}
+
+fn create_market_closing_at<T: Config>(
+	creator: T::AccountId,
+	outcome_amount: u8,
+	end: T::BlockNumber,
+) -> Result<MarketId, BenchmarkError> {
+	create_market_with_oracle::<T>(creator.clone(), outcome_amount, end, creator)
+}
+
+/// Creates a market, which waits for the acceptance of `oracle`, if it isn't the creator.
+fn create_market_with_oracle<T: Config>(
+	creator: T::AccountId,
+	outcome_amount: u8,
+	end: T::BlockNumber,
+	oracle: T::AccountId,
+) -> Result<MarketId, BenchmarkError> {
+	fund_account::<T>(&creator);
+	// The setup of some benchmarks creates more markets in a block than `MaxMarketsPerBlock`.
+	MarketsCreatedInBlock::<T>::kill();
+	let market_id = Template::<T>::market_counter();
+	Template::<T>::create_market(
+		RawOrigin::Signed(creator.clone()).into(),
+		[0u8; 32],
+		outcome_data::<T>(outcome_amount),
+		end,
+		oracle,
+		Default::default(),
+		Default::default(),
+	)
+	.map_err(|_| BenchmarkError::Stop("create_market failed"))?;
+	Ok(market_id)
+}
+
+/// Creates a market with `outcome_amount` outcomes, which are owned by distinct buyers, and
+/// moves it into the given status with an oracle report and a full ranking.
+fn market_with_bids<T: Config>(
+	outcome_amount: u8,
+	status: MarketStatus,
+) -> Result<MarketId, BenchmarkError> {
+	let market_id = create_market_with_outcomes::<T>(account("creator", 0, 0), outcome_amount)?;
+
+	let price = T::Currency::minimum_balance();
+	let mut outcomes = Outcomes::<T>::get(market_id);
+	for (i, outcome) in outcomes.iter_mut().enumerate() {
+		let buyer: T::AccountId = account("buyer", i as u32, 0);
+		fund_account::<T>(&buyer);
+		T::Currency::reserve_named(&HoldReason::OutcomeBid.identifier(), &buyer, price)?;
+		outcome.owner = buyer;
+		outcome.price = price;
+	}
+	Outcomes::<T>::insert(market_id, outcomes);
+
+	// Every outcome gets a share of the reward, which is the most expensive payout.
+	let mut payout_ratios =
+		(0..outcome_amount).map(|_| Percent::from_percent(1)).collect::<Vec<_>>();
+	payout_ratios[0] = Percent::from_percent(101u8.saturating_sub(outcome_amount));
+	PayoutRatios::<T>::insert(market_id, PayoutRatiosOf::<T>::truncate_from(payout_ratios));
+	RankedReports::<T>::insert(
+		market_id,
+		RankingOf::<T>::truncate_from((0..outcome_amount).collect::<Vec<_>>()),
+	);
+
+	Markets::<T>::mutate(market_id, |market| {
+		if let Some(market) = market {
+			market.oracle_outcome_report = Some(0);
+			market.status = status;
+		}
+	});
+	T::Currency::make_free_balance_be(
+		&Template::<T>::market_account(market_id),
+		T::Currency::minimum_balance(),
+	);
+	Ok(market_id)
+}
+
+fn pending_market<T: Config>(oracle: T::AccountId) -> Result<MarketId, BenchmarkError> {
+	let end = frame_system::Pallet::<T>::block_number().saturating_add(T::MinMarketPeriod::get());
+	create_market_with_oracle::<T>(account("creator", 0, 0), 1, end, oracle)
+}
+
+/// Moves the market into the closed status and returns its end.
+fn set_closed<T: Config>(market_id: MarketId) -> Result<T::BlockNumber, BenchmarkError> {
+	let mut market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+	market.status = MarketStatus::Closed;
+	let end = market.end;
+	Markets::<T>::insert(market_id, market);
+	Ok(end)
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
+	fn create_market(o: Linear<1, { T::MaxOutcomes::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let market_id = Template::<T>::market_counter();
+		let end =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::MinMarketPeriod::get());
+
+		#[extrinsic_call]
+		create_market(
+			RawOrigin::Signed(caller.clone()),
+			[0u8; 32],
+			outcome_data::<T>(o.saturated_into()),
+			end,
+			caller,
+			Default::default(),
+			Default::default(),
+		);
+
+		assert_eq!(Outcomes::<T>::get(market_id).len(), o as usize);
+	}
+
+	#[benchmark]
+	fn destroy_market(o: Linear<1, { T::MaxOutcomes::get() }>) -> Result<(), BenchmarkError> {
+		// Every outcome has a held bid and a standing order, and the sponsors and the scalar
+		// reports are full, which are all released by the call.
+		let market_id = market_with_bids::<T>(o.saturated_into(), MarketStatus::Active)?;
+		let amount = T::Currency::minimum_balance();
+		let sponsors = (0..T::MaxSponsors::get())
+			.map(|i| (account("sponsor", i, 0), amount))
+			.collect::<Vec<(T::AccountId, _)>>();
+		MarketSponsors::<T>::insert(market_id, SponsorsOf::<T>::truncate_from(sponsors));
+		T::Currency::make_free_balance_be(
+			&Template::<T>::market_account(market_id),
+			amount.saturating_mul(T::MaxSponsors::get().saturating_add(1).into()),
+		);
+		let mut reports = Vec::new();
+		for i in 0..T::MaxScalarOracles::get() {
+			let oracle: T::AccountId = account("oracle", i, 0);
+			fund_account::<T>(&oracle);
+			T::Currency::reserve(&oracle, T::OracleBond::get())?;
+			reports.push((oracle, 0));
+		}
+		ScalarReports::<T>::insert(market_id, ScalarReportsOf::<T>::truncate_from(reports));
+		for (i, outcome) in Outcomes::<T>::get(market_id).iter().enumerate() {
+			T::Currency::reserve_named(
+				&HoldReason::StandingOrder.identifier(),
+				&outcome.owner,
+				amount,
+			)?;
+			StandingOrders::<T>::insert(
+				market_id,
+				i as u8,
+				(&outcome.owner, outcome.price.saturating_add(amount)),
+			);
+		}
+		let origin =
+			T::DestroyOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		destroy_market(origin as T::RuntimeOrigin, market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Destroying);
+		assert!(MarketSponsors::<T>::get(market_id).is_empty());
+		assert!(ScalarReports::<T>::get(market_id).is_empty());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn continue_destroy(i: Linear<1, { T::MaxOutcomes::get() + 1 }>) -> Result<(), BenchmarkError> {
+		// The outcomes are removed one by one and the rest of the market storage as last item.
+		let outcome_amount = i.saturating_sub(1).max(1);
+		let market_id =
+			market_with_bids::<T>(outcome_amount.saturated_into(), MarketStatus::Active)?;
+		let origin =
+			T::DestroyOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Template::<T>::destroy_market(origin, market_id)?;
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		continue_destroy(RawOrigin::Signed(caller), market_id, i);
+
+		assert_eq!(Markets::<T>::contains_key(market_id), i <= outcome_amount);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_initialize(
+		m: Linear<0, { CacheSize::get() }>,
+		b: Linear<0, { T::MaxQueuedBids::get() }>,
+		a: Linear<0, 1_000>,
+	) -> Result<(), BenchmarkError> {
+		let end =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::MinMarketPeriod::get());
+		for i in 0..m {
+			create_market_closing_at::<T>(account("creator", i, 0), 1, end)?;
+		}
+
+		// Every queued bid wins on a distinct outcome, which is the expensive clearing path.
+		let max_outcomes = T::MaxOutcomes::get();
+		let mut bid_market_id = 0;
+		for i in 0..b {
+			if i % max_outcomes == 0 {
+				bid_market_id = create_market_closing_at::<T>(
+					account("bid_creator", i, 0),
+					max_outcomes.saturated_into(),
+					end.saturating_add(1u32.into()),
+				)?;
+			}
+			let buyer: T::AccountId = account("buyer", i, 0);
+			fund_account::<T>(&buyer);
+			let price = T::Currency::minimum_balance();
+			T::Currency::reserve_named(&HoldReason::OutcomeBid.identifier(), &buyer, price)?;
+			let bid = QueuedBid {
+				market_id: bid_market_id,
+				outcome_index: (i % max_outcomes).saturated_into(),
+				buyer,
+				price,
+			};
+			QueuedBids::<T>::try_append(bid).map_err(|_| BenchmarkError::Stop("queue full"))?;
+		}
+
+		for i in 0..a {
+			BidsInBlock::<T>::insert(account::<T::AccountId>("bidder", i, 0), 1);
+		}
+
+		#[block]
+		{
+			Template::<T>::on_initialize(end);
+		}
+
+		assert!(QueuedBids::<T>::get().is_empty());
+		assert!(MarketIdsPerCloseBlock::<T>::iter_key_prefix(end).next().is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_finalize() -> Result<(), BenchmarkError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let end = now.saturating_add(T::MinMarketPeriod::get());
+		for i in 0..CacheSize::get() {
+			create_market_closing_at::<T>(account("creator", i, 0), 1, end)?;
+		}
+
+		let mut transitions = MarketTransitions::default();
+		for market_id in 0..MaxTransitionsPerBlock::get() {
+			let market_id = MarketId::from(market_id);
+			transitions.closed.try_push(market_id).map_err(|_| BenchmarkError::Weightless)?;
+			transitions
+				.reported
+				.try_push(market_id)
+				.map_err(|_| BenchmarkError::Weightless)?;
+			transitions
+				.redeemed
+				.try_push(market_id)
+				.map_err(|_| BenchmarkError::Weightless)?;
+			transitions
+				.invalid
+				.try_push(market_id)
+				.map_err(|_| BenchmarkError::Weightless)?;
+		}
+		MarketTransitionsInBlock::<T>::put(transitions);
+
+		#[block]
+		{
+			Template::<T>::on_finalize(end.saturating_sub(T::CloseAnnouncementHorizon::get()));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn redeem(o: Linear<1, { T::MaxOutcomes::get() }>) -> Result<(), BenchmarkError> {
+		let market_id = market_with_bids::<T>(o.saturated_into(), MarketStatus::Reported)?;
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		redeem(RawOrigin::Signed(caller), market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Redeemed);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_storage_repatriate() -> Result<(), BenchmarkError> {
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Redeemed)?;
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		let now = market
+			.end
+			.saturating_add(T::MarketCreatorClearStorageTime::get())
+			.saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		clear_storage(RawOrigin::Signed(caller), market_id);
+
+		assert!(!Markets::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_storage_unreserve() -> Result<(), BenchmarkError> {
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Redeemed)?;
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		clear_storage(RawOrigin::Signed(market.creator), market_id);
+
+		assert!(!Markets::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn report_as_oracle() -> Result<(), BenchmarkError> {
 		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(
+			caller.clone(),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let end = set_closed::<T>(market_id)?;
+		frame_system::Pallet::<T>::set_block_number(end.saturating_add(1u32.into()));
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		report_as_oracle(RawOrigin::Signed(caller), market_id, 0);
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Reported);
+		Ok(())
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
+	fn resolve_expired_market_invalid(
+		o: Linear<1, { T::MaxOutcomes::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let market_id = market_with_bids::<T>(o.saturated_into(), MarketStatus::Closed)?;
+		let end = set_closed::<T>(market_id)?;
+		let now = Template::<T>::reporting_deadline(end).saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
 		let caller: T::AccountId = whitelisted_caller();
+
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		resolve_expired_market(RawOrigin::Signed(caller), market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Invalid);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resolve_expired_market_scalar(
+		r: Linear<1, { T::MaxScalarOracles::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let market_id = create_market_with_outcomes::<T>(
+			account("creator", 0, 0),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let end = set_closed::<T>(market_id)?;
+
+		// Every other report is an outlier, which is slashed.
+		let mut oracles = Vec::new();
+		let mut reports = Vec::new();
+		for i in 0..r {
+			let oracle: T::AccountId = account("oracle", i, 0);
+			fund_account::<T>(&oracle);
+			T::Currency::reserve(&oracle, T::OracleBond::get())?;
+			let value = if i % 2 == 0 { 0 } else { u128::MAX };
+			oracles.push(oracle.clone());
+			reports.push((oracle, value));
+		}
+		ScalarOracles::<T>::insert(market_id, ScalarOraclesOf::<T>::truncate_from(oracles));
+		ScalarRanges::<T>::insert(market_id, (0, u128::MAX));
+		ScalarReports::<T>::insert(market_id, ScalarReportsOf::<T>::truncate_from(reports));
+		let now = Template::<T>::reporting_deadline(end).saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		resolve_expired_market(RawOrigin::Signed(caller), market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Reported);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn buy_outcome() -> Result<(), BenchmarkError> {
+		// The outcome is owned, so the previous owner is refunded.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Active)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let price = T::Currency::minimum_balance().saturating_mul(2u32.into());
+		let valid_until = frame_system::Pallet::<T>::block_number();
+
+		#[extrinsic_call]
+		buy_outcome(RawOrigin::Signed(caller.clone()), market_id, 0, price, valid_until);
+
+		if !T::BatchBidClearing::get() {
+			assert_eq!(Outcomes::<T>::get(market_id)[0].owner, caller);
+		}
+		Ok(())
+	}
+
+	#[benchmark]
+	fn report_ranking_as_oracle(
+		o: Linear<1, { T::MaxOutcomes::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(
+			caller.clone(),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let payout_ratios = (0..o).map(|_| Percent::from_percent(1)).collect::<Vec<_>>();
+		PayoutRatios::<T>::insert(market_id, PayoutRatiosOf::<T>::truncate_from(payout_ratios));
+		let ranking = RankingOf::<T>::truncate_from((0..o as u8).collect::<Vec<_>>());
+		let end = set_closed::<T>(market_id)?;
+		frame_system::Pallet::<T>::set_block_number(end.saturating_add(1u32.into()));
+
+		#[extrinsic_call]
+		report_ranking_as_oracle(RawOrigin::Signed(caller), market_id, ranking);
+
+		assert_eq!(RankedReports::<T>::get(market_id).len(), o as usize);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_oracle_role() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let market_id = pending_market::<T>(caller.clone())?;
+
+		#[extrinsic_call]
+		accept_oracle_role(RawOrigin::Signed(caller), market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Active);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn replace_oracle() -> Result<(), BenchmarkError> {
+		let market_id = pending_market::<T>(account("oracle", 0, 0))?;
+		let now = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::OracleAcceptancePeriod::get())
+			.saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let oracle: T::AccountId = account("oracle", 1, 0);
+
+		#[extrinsic_call]
+		replace_oracle(RawOrigin::Signed(account("creator", 0, 0)), market_id, oracle.clone());
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.oracle, oracle);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_market() -> Result<(), BenchmarkError> {
+		let market_id = pending_market::<T>(account("oracle", 0, 0))?;
+
+		#[extrinsic_call]
+		cancel_market(RawOrigin::Signed(account("creator", 0, 0)), market_id);
+
+		assert!(!Markets::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pause_market() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+
+		#[extrinsic_call]
+		pause_market(RawOrigin::Signed(caller), market_id);
+
+		assert!(MarketPauses::<T>::get(market_id).paused_since.is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unpause_market() -> Result<(), BenchmarkError> {
+		// The end is extended, so the close is rescheduled.
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+		Template::<T>::pause_market(RawOrigin::Signed(caller.clone()).into(), market_id)?;
+		let end = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?.end;
+		let now = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+
+		#[extrinsic_call]
+		unpause_market(RawOrigin::Signed(caller), market_id, true);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert!(market.end > end);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn close_market() -> Result<(), BenchmarkError> {
+		let market_id = create_market_with_outcomes::<T>(
+			account("creator", 0, 0),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		frame_system::Pallet::<T>::set_block_number(market.end.saturating_add(1u32.into()));
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		close_market(RawOrigin::Signed(caller), market_id);
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Closed);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn configure_scalar_market() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(
+			caller.clone(),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let oracles = (0..T::MaxScalarOracles::get())
+			.map(|i| account("oracle", i, 0))
+			.collect::<Vec<T::AccountId>>();
+
+		#[extrinsic_call]
+		configure_scalar_market(
+			RawOrigin::Signed(caller),
+			market_id,
+			ScalarOraclesOf::<T>::truncate_from(oracles),
+			0,
+			u128::MAX,
+		);
+
+		assert!(ScalarRanges::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn report_scalar() -> Result<(), BenchmarkError> {
+		let market_id = create_market_with_outcomes::<T>(
+			account("creator", 0, 0),
+			T::MaxOutcomes::get().saturated_into(),
+		)?;
+		let end = set_closed::<T>(market_id)?;
+
+		// The caller submits the last missing report.
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let mut oracles = Vec::new();
+		let mut reports = Vec::new();
+		for i in 1..T::MaxScalarOracles::get() {
+			let oracle: T::AccountId = account("oracle", i, 0);
+			oracles.push(oracle.clone());
+			reports.push((oracle, 0));
+		}
+		oracles.push(caller.clone());
+		ScalarOracles::<T>::insert(market_id, ScalarOraclesOf::<T>::truncate_from(oracles));
+		ScalarRanges::<T>::insert(market_id, (0, u128::MAX));
+		ScalarReports::<T>::insert(market_id, ScalarReportsOf::<T>::truncate_from(reports));
+		frame_system::Pallet::<T>::set_block_number(end.saturating_add(1u32.into()));
+
+		#[extrinsic_call]
+		report_scalar(RawOrigin::Signed(caller), market_id, 0);
+
+		assert_eq!(ScalarReports::<T>::get(market_id).len(), T::MaxScalarOracles::get() as usize);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sponsor_market() -> Result<(), BenchmarkError> {
+		let market_id = create_market_with_outcomes::<T>(account("creator", 0, 0), 1)?;
+		let amount = T::Currency::minimum_balance();
+		// The caller is a new sponsor, which is appended to the full list of sponsors.
+		let sponsors = (1..T::MaxSponsors::get())
+			.map(|i| (account("sponsor", i, 0), amount))
+			.collect::<Vec<(T::AccountId, _)>>();
+		MarketSponsors::<T>::insert(market_id, SponsorsOf::<T>::truncate_from(sponsors));
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+
+		#[extrinsic_call]
+		sponsor_market(RawOrigin::Signed(caller), market_id, amount);
+
+		assert_eq!(MarketSponsors::<T>::get(market_id).len(), T::MaxSponsors::get() as usize);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_market_data() -> Result<(), BenchmarkError> {
+		// The trading started, so the update requires a deposit.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Active)?;
+		let creator: T::AccountId = account("creator", 0, 0);
+
+		#[extrinsic_call]
+		update_market_data(RawOrigin::Signed(creator), market_id, [1u8; 32]);
+
+		assert_eq!(DataUpdates::<T>::get(market_id), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_market_locale() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+		let locale = LocaleTag { language: *b"en", region: *b"GB" };
+		Template::<T>::set_market_locale(
+			RawOrigin::Signed(caller.clone()).into(),
+			market_id,
+			Some(locale),
+		)?;
+		let locale = LocaleTag { language: *b"en", region: *b"US" };
+
+		#[extrinsic_call]
+		set_market_locale(RawOrigin::Signed(caller), market_id, Some(locale));
+
+		assert_eq!(MarketLocales::<T>::get(market_id), Some(locale));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_refund_participant() -> Result<(), BenchmarkError> {
+		// The participant owns every outcome.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Active)?;
+		let who: T::AccountId = account("participant", 0, 0);
+		fund_account::<T>(&who);
+		let mut outcomes = Outcomes::<T>::get(market_id);
+		for outcome in outcomes.iter_mut() {
+			T::Currency::reserve_named(&HoldReason::OutcomeBid.identifier(), &who, outcome.price)?;
+			outcome.owner = who.clone();
+		}
+		Outcomes::<T>::insert(market_id, outcomes);
+		let origin =
+			T::ModerationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		force_refund_participant(origin as T::RuntimeOrigin, market_id, who.clone());
+
+		assert!(Outcomes::<T>::get(market_id).iter().all(|outcome| outcome.owner != who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn top_up_bond() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+		RequiredBonds::<T>::insert(market_id, T::CreatorBond::get().saturating_mul(2u32.into()));
+
+		#[extrinsic_call]
+		top_up_bond(RawOrigin::Signed(caller), market_id);
+
+		assert!(!RequiredBonds::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn require_bond_top_up(
+		m: Linear<1, { SettlementBatchSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// Every market has a pot above the threshold and a bond below the requirement.
+		let mut market_ids = Vec::new();
+		for _ in 0..m {
+			let market_id = market_with_bids::<T>(
+				T::MaxOutcomes::get().saturated_into(),
+				MarketStatus::Active,
+			)?;
+			Markets::<T>::mutate(market_id, |market| {
+				if let Some(market) = market {
+					market.bond = Zero::zero();
+				}
+			});
+			market_ids.push(market_id);
+		}
+		let origin =
+			T::BondTopUpOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		require_bond_top_up(
+			origin as T::RuntimeOrigin,
+			BoundedVec::truncate_from(market_ids.clone()),
+			Zero::zero(),
+		);
+
+		assert!(market_ids.iter().all(|market_id| RequiredBonds::<T>::contains_key(market_id)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn retire_outcome() -> Result<(), BenchmarkError> {
+		// The outcome is traded, so the bid of the owner is refunded.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Active)?;
+		let origin =
+			T::ModerationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		retire_outcome(origin as T::RuntimeOrigin, market_id, 0);
+
+		assert!(RetiredOutcomes::<T>::get(market_id).contains(&0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn attest_resolution() -> Result<(), BenchmarkError> {
+		let market_id = market_with_bids::<T>(1, MarketStatus::Reported)?;
+		let attestation =
+			AttestationOf::<T>::truncate_from(vec![0u8; T::MaxAttestationLength::get() as usize]);
+
+		#[extrinsic_call]
+		attest_resolution(RawOrigin::Signed(account("creator", 0, 0)), market_id, attestation);
+
+		assert!(ResolutionAttestations::<T>::contains_key(market_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_max_bid() -> Result<(), BenchmarkError> {
+		// The previous standing order of the owner is released.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Active)?;
+		let owner: T::AccountId = account("buyer", 0, 0);
+		let price = T::Currency::minimum_balance();
+		Template::<T>::set_max_bid(
+			RawOrigin::Signed(owner.clone()).into(),
+			market_id,
+			0,
+			Some(price.saturating_mul(2u32.into())),
+		)?;
+		let max_price = price.saturating_mul(3u32.into());
+
+		#[extrinsic_call]
+		set_max_bid(RawOrigin::Signed(owner.clone()), market_id, 0, Some(max_price));
+
+		assert_eq!(StandingOrders::<T>::get(market_id, 0), Some((owner, max_price)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_market_manager() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+		let managers = (1..T::MaxManagers::get())
+			.map(|i| account("manager", i, 0))
+			.collect::<Vec<T::AccountId>>();
+		MarketManagers::<T>::insert(market_id, BoundedVec::truncate_from(managers));
+		let manager: T::AccountId = account("manager", 0, 0);
+
+		#[extrinsic_call]
+		add_market_manager(RawOrigin::Signed(caller), market_id, manager.clone());
+
+		assert!(MarketManagers::<T>::get(market_id).contains(&manager));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_market_manager() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let market_id = create_market_with_outcomes::<T>(caller.clone(), 1)?;
+		let managers = (0..T::MaxManagers::get())
+			.map(|i| account("manager", i, 0))
+			.collect::<Vec<T::AccountId>>();
+		MarketManagers::<T>::insert(market_id, BoundedVec::truncate_from(managers));
+		let manager: T::AccountId = account("manager", T::MaxManagers::get().saturating_sub(1), 0);
+
+		#[extrinsic_call]
+		remove_market_manager(RawOrigin::Signed(caller), market_id, manager.clone());
+
+		assert!(!MarketManagers::<T>::get(market_id).contains(&manager));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn emit_highest_outcomes(
+		c: Linear<1, { MaxHighestOutcomes::get() }>,
+	) -> Result<(), BenchmarkError> {
+		for i in 0..c.saturating_mul(2) {
+			create_market_with_outcomes::<T>(
+				account("creator", i, 0),
+				T::MaxOutcomes::get().saturated_into(),
+			)?;
+		}
+
+		#[block]
+		{
+			Template::<T>::emit_highest_outcomes(c as usize);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resolve_market_outcome() -> Result<(), BenchmarkError> {
+		// A closed market isn't reported yet, so the transition is noted.
+		let market_id =
+			market_with_bids::<T>(T::MaxOutcomes::get().saturated_into(), MarketStatus::Closed)?;
+
+		#[block]
+		{
+			assert!(Template::<T>::resolve_market(&market_id, Resolution::Outcome(0)).is_ok());
+		}
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Reported);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resolve_market_invalid(
+		o: Linear<1, { T::MaxOutcomes::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let market_id = market_with_bids::<T>(o.saturated_into(), MarketStatus::Closed)?;
+
+		#[block]
+		{
+			assert!(Template::<T>::resolve_market(&market_id, Resolution::Invalid).is_ok());
+		}
+
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Invalid);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn dispute_market() -> Result<(), BenchmarkError> {
+		let market_id = market_with_bids::<T>(1, MarketStatus::Reported)?;
+
+		#[block]
+		{
+			assert!(Template::<T>::dispute_market(&market_id).is_ok());
+		}
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		let market = Markets::<T>::get(market_id).ok_or(BenchmarkError::Weightless)?;
+		assert_eq!(market.status, MarketStatus::Disputed);
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -8,8 +8,11 @@ pub use pallet::*;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-	pallet_prelude::Weight,
-	sp_runtime::traits::{CheckedDiv, Zero},
+	pallet_prelude::{BoundedVec, Weight},
+	sp_runtime::{
+		traits::{CheckedDiv, Zero},
+		Percent,
+	},
 };
 
 use scale_info::TypeInfo;
@@ -21,18 +24,43 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod replay;
+
 // TODO 3: What is this and what does it do?
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod mechanism;
+pub mod migrations;
+pub mod weights;
+pub use mechanism::Mechanism;
+pub use weights::WeightInfo;
+
 pub type MarketId = u128;
 
+/// The version of the market account derivation used by `Pallet::market_account`.
+pub const MARKET_ACCOUNT_VERSION: u8 = 1;
+
+/// The prefix of the `DigestItem::Other` which contains the encoded `MarketTransitions`.
+pub const MARKET_TRANSITIONS_DIGEST_PREFIX: [u8; 4] = *b"mkts";
+
+pub type MaxTransitionsPerBlock = frame_support::pallet_prelude::ConstU32<256>;
+
 #[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
 pub enum MarketStatus {
 	Active,
 	Closed,
 	Reported,
 	Redeemed,
+	Invalid,
+	PendingOracle,
+	/// The oracle report is challenged. The funds of the market are frozen until the external
+	/// resolver resolves the market.
+	Disputed,
+	/// The funds of the market are released and its storage is removed in steps by
+	/// `continue_destroy` and `on_idle`.
+	Destroying,
 }
 
 #[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
@@ -53,6 +81,208 @@ pub struct Outcome<AccountId, Balance> {
 	pub price: Balance,
 }
 
+/// The reasons for which the pallet holds funds on the accounts of the market participants.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoldReason {
+	/// The price of an owned or queued outcome bid.
+	OutcomeBid,
+	/// The difference between the maximum price of a standing order and the outcome price.
+	StandingOrder,
+}
+
+impl HoldReason {
+	/// The identifier of the named reserve.
+	pub fn identifier(&self) -> [u8; 8] {
+		match self {
+			HoldReason::OutcomeBid => *b"mkt/obid",
+			HoldReason::StandingOrder => *b"mkt/sord",
+		}
+	}
+}
+
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct QueuedBid<AccountId, Balance> {
+	pub market_id: MarketId,
+	pub outcome_index: u8,
+	pub buyer: AccountId,
+	pub price: Balance,
+}
+
+/// What happened to the creator bond when the market storage was removed.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub enum BondDisposition<AccountId> {
+	/// The creator bond was returned to the creator.
+	ReturnedToCreator,
+	/// The creator bond was paid to the account, which cleared the storage.
+	PaidTo(AccountId),
+}
+
+/// The language and region of a market as ISO 639-1 and ISO 3166-1 alpha-2 codes, for example
+/// `*b"en"` and `*b"GB"`.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocaleTag {
+	pub language: [u8; 2],
+	pub region: [u8; 2],
+}
+
+/// A bid of the per-market bid log.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct BidRecord<AccountId, Balance, BlockNumber> {
+	pub buyer: AccountId,
+	pub outcome_index: u8,
+	pub price: Balance,
+	pub block: BlockNumber,
+}
+
+/// The market status changes of one block. If one of the lists is full, `overflowed` is set and
+/// further market ids are dropped.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MarketTransitions {
+	pub closed: BoundedVec<MarketId, MaxTransitionsPerBlock>,
+	pub reported: BoundedVec<MarketId, MaxTransitionsPerBlock>,
+	pub redeemed: BoundedVec<MarketId, MaxTransitionsPerBlock>,
+	pub invalid: BoundedVec<MarketId, MaxTransitionsPerBlock>,
+	pub overflowed: bool,
+}
+
+impl MarketTransitions {
+	pub fn is_empty(&self) -> bool {
+		self.closed.is_empty()
+			&& self.reported.is_empty()
+			&& self.redeemed.is_empty()
+			&& self.invalid.is_empty()
+	}
+}
+
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PauseState<BlockNumber> {
+	pub paused_since: Option<BlockNumber>,
+	pub total_paused: BlockNumber,
+}
+
+/// An outcome position of an account in an unsettled market.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct PositionInfo<Balance> {
+	pub market_id: MarketId,
+	pub outcome_index: u8,
+	pub status: MarketStatus,
+	/// The amount held for the outcome bid.
+	pub escrowed: Balance,
+	/// The amount the account receives on redemption of a reported market.
+	pub claimable: Balance,
+}
+
+/// The flows of a participant of a resolved market.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct SettlementEntry<AccountId, Balance> {
+	pub account: AccountId,
+	/// The amount the participant paid into the market for outcome positions.
+	pub paid_in: Balance,
+	/// The amount the participant received from the market on resolution.
+	pub paid_out: Balance,
+}
+
+/// The economically relevant parameters of the pallet for frontends.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct MarketConstants<Balance, BlockNumber> {
+	pub creator_bond: Balance,
+	pub oracle_bond: Balance,
+	pub keeper_tip: Balance,
+	pub min_market_period: BlockNumber,
+	pub max_pause_duration: BlockNumber,
+	pub oracle_acceptance_period: BlockNumber,
+	pub reporting_period: BlockNumber,
+	pub oracle_grace_period: BlockNumber,
+	pub market_creator_clear_storage_time: BlockNumber,
+	pub max_outcomes: u32,
+	pub max_bids_per_block: u32,
+	pub max_queued_bids: u32,
+	pub max_sponsors: u32,
+	pub max_markets_per_close_block: u32,
+	pub bid_log_length: u32,
+	pub batch_bid_clearing: bool,
+}
+
+/// The health of the markets for governance and monitoring.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MarketMetrics<Balance> {
+	/// The closed markets, whose reporting window expired without a report.
+	pub stuck_closed: u32,
+	/// The reported or resolved markets, which are still in storage after the period of the
+	/// creator to clear them.
+	pub past_redemption_deadline: u32,
+	/// The balances of the market accounts and the bids held for unresolved markets.
+	pub total_value_locked: Balance,
+	/// The creator and oracle bonds of all markets.
+	pub bonds_reserved: Balance,
+}
+
+/// The parameters of `create_market`, which can be validated without creating the market.
+#[derive(Decode, Encode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct MarketParams<AccountId, BlockNumber> {
+	pub creator: AccountId,
+	/// The hashes of the off-chain content of each outcome.
+	pub outcome_data: alloc::vec::Vec<[u8; 32]>,
+	pub end: BlockNumber,
+	pub oracle: AccountId,
+	pub payout_ratios: alloc::vec::Vec<Percent>,
+	pub mechanism: Mechanism,
+}
+
+/// The reasons why `create_market` would fail for the given `MarketParams`.
+#[derive(Decode, Encode, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CreateMarketError {
+	/// The creator can't reserve the creator bond (and the oracle bond, if they are the oracle).
+	InsufficientCreatorBalance,
+	OutcomeAmountTooLow,
+	TooManyOutcomes,
+	/// The outcome data hashes aren't unique.
+	DuplicateOutcomeData,
+	InvalidPayoutRatios,
+	BelowMinMarketPeriod,
+	MarketCounterOverflow,
+	/// The close block of the market already contains the maximum amount of markets.
+	CloseScheduleFull,
+	/// The trading mechanism isn't implemented yet.
+	MechanismNotSupported,
+	/// The current block already contains the maximum amount of new markets.
+	TooManyMarketsThisBlock,
+}
+
+/// The reasons why `buy_outcome` would fail for the given market, outcome and price.
+#[derive(Decode, Encode, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BidError {
+	MarketNotFound,
+	MarketNotActive,
+	MarketPaused,
+	/// The creator has to top up the bond before the market accepts bids.
+	BondTopUpRequired,
+	InvalidOutcomeIndex,
+	/// The outcome was retired and can't be bought.
+	OutcomeRetired,
+	/// The price isn't higher than the current price of the outcome.
+	PriceTooLow,
+	/// The outcome was already bought and the mechanism of the market doesn't allow outbids.
+	OutcomeAlreadyOwned,
+}
+
+/// The effect `buy_outcome` would have.
+#[derive(Decode, Encode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct BuyQuote<AccountId, Balance> {
+	/// The outbid owner and their refunded bid, if the outcome has a bid.
+	pub refund: Option<(AccountId, Balance)>,
+	/// The price, at which the standing order of the owner outbids the bid right away.
+	pub rebid: Option<Balance>,
+	/// The outcome with the highest price after the bid.
+	pub favorite: u8,
+	/// The market pot after the bid.
+	pub pot: Balance,
+	/// The fees, which are taken from the pot on redemption.
+	pub fees: Balance,
+	/// The bid is queued and only cleared in the next block, where it can still be outbid.
+	pub queued: bool,
+}
+
 // TODO 4: What are `CheckedDiv + Zero` called?
 // TODO 5: Why can't we just remove `CheckedDiv`?
 // TODO 6: What does `CheckedDiv + Zero` mean for `Balance`?
@@ -62,39 +292,73 @@ impl<AccountId, Balance: CheckedDiv + Zero> Outcome<AccountId, Balance> {
 	}
 }
 
-pub trait WeightInfo {
-	fn do_something() -> Weight;
-}
-
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use alloc::vec::Vec;
+	use alloc::{
+		collections::{BTreeMap, BTreeSet},
+		vec::Vec,
+	};
 	use frame_support::{
 		pallet_prelude::*,
-		sp_runtime::traits::{CheckedSub, One, Saturating},
+		sp_runtime::{
+			traits::{CheckedSub, One, Saturating},
+			Perbill,
+		},
+		storage::with_storage_layer,
 		traits::{
-			BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency, WithdrawReasons,
+			BalanceStatus, Contains, Currency, ExistenceRequirement, NamedReservableCurrency,
+			OnUnbalanced, ReservableCurrency, WithdrawReasons,
 		},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 
-	impl<T: Config> WeightInfo for Pallet<T> {
-		fn do_something() -> Weight {
-			Weight::from(1_000_000_000u64)
-		}
-	}
-
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 	pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+	pub type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
 	pub type MarketOf<T> = Market<AccountIdOf<T>, BlockNumberFor<T>, BalanceOf<T>>;
 	pub type OutcomesOf<T> =
 		BoundedVec<Outcome<AccountIdOf<T>, BalanceOf<T>>, <T as Config>::MaxOutcomes>;
+	pub type OutcomeDataOf<T> = BoundedVec<[u8; 32], <T as Config>::MaxOutcomes>;
+	pub type PayoutRatiosOf<T> = BoundedVec<Percent, <T as Config>::MaxOutcomes>;
+	pub type RankingOf<T> = BoundedVec<u8, <T as Config>::MaxOutcomes>;
+	pub type SettlementReportOf<T> =
+		BoundedVec<SettlementEntry<AccountIdOf<T>, BalanceOf<T>>, MaxSettlementEntries<T>>;
+	pub type AttestationOf<T> = BoundedVec<u8, <T as Config>::MaxAttestationLength>;
+	pub type BidLogOf<T> = BoundedVec<
+		BidRecord<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		<T as Config>::BidLogLength,
+	>;
+	pub type ScalarOraclesOf<T> = BoundedVec<AccountIdOf<T>, <T as Config>::MaxScalarOracles>;
+	pub type ScalarReportsOf<T> =
+		BoundedVec<(AccountIdOf<T>, u128), <T as Config>::MaxScalarOracles>;
+	pub type SponsorsOf<T> = BoundedVec<(AccountIdOf<T>, BalanceOf<T>), <T as Config>::MaxSponsors>;
+	pub type QueuedBidsOf<T> =
+		BoundedVec<QueuedBid<AccountIdOf<T>, BalanceOf<T>>, <T as Config>::MaxQueuedBids>;
 
 	pub type CacheSize = frame_support::pallet_prelude::ConstU32<64>;
+	/// The maximum number of markets in a single `settle_markets` call.
+	pub type SettlementBatchSize = frame_support::pallet_prelude::ConstU32<32>;
+	/// The maximum number of `HighestOutcome` events in one `on_idle`, which is benchmarked.
+	pub type MaxHighestOutcomes = frame_support::pallet_prelude::ConstU32<100>;
+	/// The maximum number of market categories and the maximum length of a category name.
+	pub type MaxCategories = frame_support::pallet_prelude::ConstU32<64>;
+	pub type MaxCategoryLength = frame_support::pallet_prelude::ConstU32<32>;
+	pub type CategoryOf = BoundedVec<u8, MaxCategoryLength>;
+
+	/// The maximum number of entries of a settlement report: one per outcome owner and one per
+	/// fee payee, which are the creator, the referrer and the protocol beneficiary.
+	pub struct MaxSettlementEntries<T>(core::marker::PhantomData<T>);
+
+	impl<T: Config> Get<u32> for MaxSettlementEntries<T> {
+		fn get() -> u32 {
+			T::MaxOutcomes::get().saturating_add(3)
+		}
+	}
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -104,24 +368,136 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// If enabled, bids are queued and cleared at the start of the next block, so that the
+		/// highest bid per outcome wins independently of the transaction order in a block.
+		#[pallet::constant]
+		type BatchBidClearing: Get<bool>;
+
+		/// The amount, by which a standing order outbids a bid.
+		#[pallet::constant]
+		type BidIncrement: Get<BalanceOf<Self>>;
+
+		/// The number of the most recent bids kept in the bid log of each market.
+		#[pallet::constant]
+		type BidLogLength: Get<u32>;
+
+		/// The origin, which requires markets with a large pot to top up their creator bond.
+		type BondTopUpOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The currency to reserve the creator bonds and to hold the outcome bids, which are
+		/// only transferred to the market account on redemption.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+
+		/// The number of blocks, for which the markets to close are announced in advance.
+		#[pallet::constant]
+		type CloseAnnouncementHorizon: Get<Self::BlockNumber>;
+
+		/// The non-refundable fee of `create_market`, which discourages the creation of spam
+		/// markets.
+		#[pallet::constant]
+		type CreationFee: Get<BalanceOf<Self>>;
+
+		/// The accounts, which create markets without the `CreationFee`.
+		type CreationFeeExempt: Contains<Self::AccountId>;
 
 		#[pallet::constant]
 		type CreatorBond: Get<BalanceOf<Self>>;
 
+		/// The deposit of the first data update after trading started. Every further update
+		/// requires one more multiple of it. The deposit is added to the creator bond.
+		#[pallet::constant]
+		type DataUpdateDeposit: Get<BalanceOf<Self>>;
+
 		type DestroyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The fees, which are taken from the market pot on redemption, and the tip of the keeper,
+		/// who closes an overdue market.
+		type FeeSchedule: FeeSchedule<Self::AccountId, BalanceOf<Self>>;
+
+		/// If disabled, the purely informational events `MarketsToClose` and
+		/// `HighestOutcome` aren't emitted. Events of state transitions are always emitted.
+		#[pallet::constant]
+		type InformationalEvents: Get<bool>;
+
 		#[pallet::constant]
 		type MarketCreatorClearStorageTime: Get<Self::BlockNumber>;
 
+		/// The maximum length of a resolution attestation.
+		#[pallet::constant]
+		type MaxAttestationLength: Get<u32>;
+
+		/// The maximum number of `buy_outcome` calls a single account can make per block.
+		#[pallet::constant]
+		type MaxBidsPerBlock: Get<u32>;
+
+		/// The maximum number of data updates of a market after trading started.
+		#[pallet::constant]
+		type MaxDataUpdates: Get<u32>;
+
+		/// The maximum number of managers of a market besides the creator.
+		#[pallet::constant]
+		type MaxManagers: Get<u32>;
+
+		/// The maximum number of markets, which can be created in a single block.
+		#[pallet::constant]
+		type MaxMarketsPerBlock: Get<u32>;
+
 		#[pallet::constant]
 		type MaxOutcomes: Get<u32>;
 
+		/// The maximum number of blocks a market can be paused by its creator in total.
+		#[pallet::constant]
+		type MaxPauseDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of bids which can be queued in one block for batch clearing.
+		#[pallet::constant]
+		type MaxQueuedBids: Get<u32>;
+
+		/// The maximum number of oracles, which report the value of a scalar market.
+		#[pallet::constant]
+		type MaxScalarOracles: Get<u32>;
+
+		/// The maximum number of distinct sponsors of a market pot.
+		#[pallet::constant]
+		type MaxSponsors: Get<u32>;
+
 		#[pallet::constant]
 		type MinMarketPeriod: Get<Self::BlockNumber>;
 
+		/// The origin, which refunds single participants, for example after a fraud appeal.
+		type ModerationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The handler of the withdrawn `CreationFee`.
+		type OnCreationFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Notified about every bid and settlement of an account.
+		type OnParticipation: OnParticipation<Self::AccountId, BalanceOf<Self>>;
+
+		/// The number of blocks the designated oracle has to accept the oracle role.
+		#[pallet::constant]
+		type OracleAcceptancePeriod: Get<Self::BlockNumber>;
+
+		/// The bond the oracle reserves on acceptance. It's returned on report and slashed if
+		/// the market has to be resolved as invalid.
+		#[pallet::constant]
+		type OracleBond: Get<BalanceOf<Self>>;
+
+		/// The number of blocks after the reporting period in which a late oracle report is
+		/// still accepted, before the market can be resolved as invalid.
+		#[pallet::constant]
+		type OracleGracePeriod: Get<Self::BlockNumber>;
+
 		type PalletId: Get<PalletId>;
 
+		/// The number of blocks after the market end in which the oracle is expected to report.
+		#[pallet::constant]
+		type ReportingPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum distance of a scalar report to the median as part of the scalar range.
+		/// The `OracleBond` of reports further away is slashed.
+		#[pallet::constant]
+		type ScalarOutlierTolerance: Get<Percent>;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -136,34 +512,432 @@ pub mod pallet {
 	pub type MarketCounter<T: Config> =
 		StorageValue<_, MarketId, ValueQuery, DefaultMarketCounter<T>>;
 
+	/// The markets. The counter gives the number of existing markets without iterating.
 	#[pallet::storage]
 	pub type Markets<T: Config> =
-		StorageMap<_, Blake2_128Concat, MarketId, MarketOf<T>, OptionQuery>;
+		CountedStorageMap<_, Blake2_128Concat, MarketId, MarketOf<T>, OptionQuery>;
 
 	#[pallet::storage]
 	pub type Outcomes<T: Config> =
-		StorageMap<_, Blake2_128Concat, MarketId, OutcomesOf<T>, ValueQuery>;
+		CountedStorageMap<_, Blake2_128Concat, MarketId, OutcomesOf<T>, ValueQuery>;
 
+	/// The markets which close at the start of the block. Adding or removing a market doesn't
+	/// touch the other markets of the same block.
 	#[pallet::storage]
-	pub type MarketIdsPerCloseBlock<T: Config> = StorageMap<
+	pub type MarketIdsPerCloseBlock<T: Config> = StorageDoubleMap<
 		_,
+		Twox64Concat,
+		T::BlockNumber,
 		Blake2_128Concat,
+		MarketId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The number of markets in `MarketIdsPerCloseBlock` for the block, which is bounded by
+	/// `CacheSize`.
+	#[pallet::storage]
+	pub type MarketCountPerCloseBlock<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	/// The markets, which close at the start of the block, for the blocks within the
+	/// `CloseAnnouncementHorizon`. Oracles and keepers can prepare for the closes in advance.
+	#[pallet::storage]
+	pub type AnnouncedCloses<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<MarketId, CacheSize>, ValueQuery>;
+
+	/// The closed markets, whose reporting window expires before the block. Markets, which are
+	/// still unreported at the start of the block, are resolved as invalid.
+	#[pallet::storage]
+	pub type MarketIdsPerReportDeadline<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
 		T::BlockNumber,
-		BoundedVec<MarketId, CacheSize>,
+		Blake2_128Concat,
+		MarketId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The number of markets in `MarketIdsPerReportDeadline` for the block, which is bounded by
+	/// `CacheSize`.
+	#[pallet::storage]
+	pub type MarketCountPerReportDeadline<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	/// The number of bids per account in the current block. Cleared in `on_initialize`.
+	#[pallet::storage]
+	pub type BidsInBlock<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The number of markets created in the current block. Reset in `on_initialize`.
+	#[pallet::storage]
+	pub type MarketsCreatedInBlock<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The share of the reward per ranked position, if the market doesn't pay everything to the
+	/// winner.
+	#[pallet::storage]
+	pub type PayoutRatios<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, PayoutRatiosOf<T>, ValueQuery>;
+
+	/// The outcome indices reported by the oracle ordered by rank.
+	#[pallet::storage]
+	pub type RankedReports<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, RankingOf<T>, ValueQuery>;
+
+	/// The in and out flows of each participant of a resolved market. The reports are kept after
+	/// the market storage is cleared to allow accounting exports.
+	#[pallet::storage]
+	pub type SettlementReports<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, SettlementReportOf<T>, OptionQuery>;
+
+	/// The attester and the attestation of the resolution of a market, for example a signature
+	/// over the result and the hash of the signed media. Kept like the settlement report.
+	#[pallet::storage]
+	pub type ResolutionAttestations<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, (T::AccountId, AttestationOf<T>), OptionQuery>;
+
+	/// The most recent bids of each market, oldest first.
+	#[pallet::storage]
+	pub type BidLogs<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, BidLogOf<T>, ValueQuery>;
+
+	/// The oracles of a scalar market. The outcomes of a scalar market are equally sized buckets
+	/// of the `ScalarRanges` and the market resolves to the bucket of the median report.
+	#[pallet::storage]
+	pub type ScalarOracles<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, ScalarOraclesOf<T>, OptionQuery>;
+
+	/// The inclusive lower and exclusive upper bound of the value of a scalar market.
+	#[pallet::storage]
+	pub type ScalarRanges<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, (u128, u128), OptionQuery>;
+
+	/// The values reported by the oracles of a scalar market. Each report holds the
+	/// `OracleBond` of the oracle until the market is resolved.
+	#[pallet::storage]
+	pub type ScalarReports<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, ScalarReportsOf<T>, ValueQuery>;
+
+	/// The sponsors of the market pot and their contributions. The pot is held in the market
+	/// account and paid out with the outcome bids on redemption.
+	#[pallet::storage]
+	pub type MarketSponsors<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, SponsorsOf<T>, ValueQuery>;
+
+	/// The optional locale of a market.
+	#[pallet::storage]
+	pub type MarketLocales<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, LocaleTag, OptionQuery>;
+
+	/// The markets of each region.
+	#[pallet::storage]
+	pub type MarketsByRegion<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, [u8; 2], Blake2_128Concat, MarketId, (), OptionQuery>;
+
+	/// The creator bond a market has to top up to before it accepts further bids.
+	#[pallet::storage]
+	pub type RequiredBonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, BalanceOf<T>, OptionQuery>;
+
+	/// The owners and prices of the outcomes at the close of the market. Settlement uses the
+	/// snapshot, so later changes to the outcomes don't affect it.
+	#[pallet::storage]
+	pub type ClosedSnapshots<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, OutcomesOf<T>, OptionQuery>;
+
+	/// The number of data updates of a market after trading started.
+	#[pallet::storage]
+	pub type DataUpdates<T: Config> = StorageMap<_, Blake2_128Concat, MarketId, u32, ValueQuery>;
+
+	/// The trading mechanism of each market. Markets without an entry use the default mechanism.
+	#[pallet::storage]
+	pub type MarketMechanisms<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, Mechanism, ValueQuery>;
+
+	/// The owner and the maximum price of the standing order of an outcome. The owner rebids
+	/// automatically, when the outcome is outbid below the maximum price.
+	#[pallet::storage]
+	pub type StandingOrders<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		MarketId,
+		Twox64Concat,
+		u8,
+		(T::AccountId, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The accounts, which can perform the creator actions of a market besides the creator. The
+	/// bond and the creator fees stay with the creator.
+	#[pallet::storage]
+	pub type MarketManagers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		MarketId,
+		BoundedVec<T::AccountId, T::MaxManagers>,
 		ValueQuery,
 	>;
 
+	/// The markets in the `Destroying` status, whose storage isn't removed yet.
+	#[pallet::storage]
+	pub type MarketsToDestroy<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, (), OptionQuery>;
+
+	/// The outcomes of a market, which can't be bought and can't win the market anymore.
+	#[pallet::storage]
+	pub type RetiredOutcomes<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, BoundedVec<u8, T::MaxOutcomes>, ValueQuery>;
+
+	/// The outcomes of a market, which received a bid. An outcome stays traded, if the bid is
+	/// refunded later.
+	#[pallet::storage]
+	pub type TradedOutcomes<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, BoundedVec<u8, T::MaxOutcomes>, ValueQuery>;
+
+	/// The markets, in which an account owns outcomes with a bid. Entries are removed, when the
+	/// market is settled.
+	#[pallet::storage]
+	pub type AccountPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		MarketId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The bond of the oracle, who accepted the oracle role.
+	#[pallet::storage]
+	pub type OracleBonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, BalanceOf<T>, OptionQuery>;
+
+	/// The block until which the designated oracle can accept the oracle role.
+	#[pallet::storage]
+	pub type OracleAcceptanceDeadlines<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	pub type MarketPauses<T: Config> =
+		StorageMap<_, Blake2_128Concat, MarketId, PauseState<T::BlockNumber>, ValueQuery>;
+
+	/// The market status changes of the current block. Light clients can follow the market
+	/// activity with a single storage proof of this value or with the block digest.
+	#[pallet::storage]
+	pub type MarketTransitionsInBlock<T: Config> = StorageValue<_, MarketTransitions, ValueQuery>;
+
+	/// The bids of the current block, which are cleared in the next `on_initialize`.
+	#[pallet::storage]
+	pub type QueuedBids<T: Config> = StorageValue<_, QueuedBidsOf<T>, ValueQuery>;
+
+	/// The categories, under which frontends list the markets. They are set at genesis.
+	#[pallet::storage]
+	pub type Categories<T: Config> =
+		StorageValue<_, BoundedVec<CategoryOf, MaxCategories>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The creator, the outcome amount and the end of each demo market. The creator is the
+		/// oracle of the market.
+		pub demo_markets: Vec<(T::AccountId, u8, T::BlockNumber)>,
+		/// The names of the initial market categories.
+		pub categories: Vec<Vec<u8>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { demo_markets: Vec::new(), categories: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let categories = self
+				.categories
+				.iter()
+				.map(|category| CategoryOf::try_from(category.clone()))
+				.collect::<Result<Vec<_>, _>>()
+				.ok()
+				.and_then(|categories| BoundedVec::try_from(categories).ok())
+				.expect("too many or too long categories in the genesis config");
+			<Categories<T>>::put(categories);
+
+			for (creator, outcome_amount, end) in &self.demo_markets {
+				let outcome_data = (0..*outcome_amount).map(|i| [i; 32]).collect::<Vec<_>>();
+				let res = Pallet::<T>::create_market(
+					frame_system::RawOrigin::Signed(creator.clone()).into(),
+					[0u8; 32],
+					OutcomeDataOf::<T>::truncate_from(outcome_data),
+					*end,
+					creator.clone(),
+					Default::default(),
+					Mechanism::EnglishAuction,
+				);
+				assert!(res.is_ok(), "invalid demo market in the genesis config: {:?}", res);
+				// The per-block cap doesn't apply to the demo markets.
+				<MarketsCreatedInBlock<T>>::kill();
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		MarketCreated { market_id: MarketId, creator: T::AccountId },
 		MarketDestroyed { market_id: MarketId },
 		OutcomeBought { market_id: MarketId, outcome_index: u8, buyer: T::AccountId },
-		MarketsToCloseNextBlock { market_ids: Vec<MarketId> },
+		MarketsToClose { close_block: T::BlockNumber, market_ids: BoundedVec<MarketId, CacheSize> },
 		MarketClosed { market_id: MarketId },
 		MarketReported { market_id: MarketId, oracle_report_outcome: u8 },
 		MarketRedeemed { market_id: MarketId, winner_outcome: u8, winner: T::AccountId },
-		HighestOutcome { market_id: MarketId, highest_outcome: Option<u8> },
+		HighestOutcome { market_id: MarketId, highest_outcome: Option<u8>, seed: T::Hash },
+		BidQueued {
+			market_id: MarketId,
+			outcome_index: u8,
+			buyer: T::AccountId,
+			price: BalanceOf<T>,
+		},
+		BidRefunded {
+			market_id: MarketId,
+			outcome_index: u8,
+			buyer: T::AccountId,
+			price: BalanceOf<T>,
+		},
+		MarketRankingReported {
+			market_id: MarketId,
+			ranking: RankingOf<T>,
+		},
+		PositionPaidOut {
+			market_id: MarketId,
+			outcome_index: u8,
+			owner: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		MarketResolvedInvalid {
+			market_id: MarketId,
+		},
+		OracleAccepted {
+			market_id: MarketId,
+			oracle: T::AccountId,
+		},
+		OracleReplaced {
+			market_id: MarketId,
+			oracle: T::AccountId,
+		},
+		MarketCancelled {
+			market_id: MarketId,
+		},
+		MarketDisputed {
+			market_id: MarketId,
+		},
+		BondTopUpRequired {
+			market_id: MarketId,
+			required: BalanceOf<T>,
+		},
+		BondToppedUp {
+			market_id: MarketId,
+			amount: BalanceOf<T>,
+		},
+		KeeperTipPaid {
+			market_id: MarketId,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		MarketsSettled {
+			settled: u32,
+		},
+		ScalarMarketConfigured {
+			market_id: MarketId,
+			lower: u128,
+			upper: u128,
+		},
+		ScalarValueReported {
+			market_id: MarketId,
+			oracle: T::AccountId,
+			value: u128,
+		},
+		ScalarMarketResolved {
+			market_id: MarketId,
+			median: u128,
+			outcome_index: u8,
+		},
+		ScalarReporterSlashed {
+			market_id: MarketId,
+			oracle: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The terminal event of a market, whose storage was removed.
+		MarketRemoved {
+			market_id: MarketId,
+			status: MarketStatus,
+			winner: Option<T::AccountId>,
+			total_volume: BalanceOf<T>,
+			bond: BondDisposition<T::AccountId>,
+		},
+		MarketDataUpdated {
+			market_id: MarketId,
+			data: [u8; 32],
+			deposit: BalanceOf<T>,
+		},
+		MarketLocaleSet {
+			market_id: MarketId,
+			locale: Option<LocaleTag>,
+		},
+		MarketSponsored {
+			market_id: MarketId,
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		SponsorRefunded {
+			market_id: MarketId,
+			sponsor: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		OutcomeRetired {
+			market_id: MarketId,
+			outcome_index: u8,
+			/// The previous owner of the outcome and their refunded bid.
+			refund: Option<(T::AccountId, BalanceOf<T>)>,
+		},
+		ResolutionAttested {
+			market_id: MarketId,
+			attester: T::AccountId,
+		},
+		StandingOrderSet {
+			market_id: MarketId,
+			outcome_index: u8,
+			who: T::AccountId,
+			max_price: Option<BalanceOf<T>>,
+		},
+		StandingOrderRebid {
+			market_id: MarketId,
+			outcome_index: u8,
+			owner: T::AccountId,
+			price: BalanceOf<T>,
+		},
+		MarketManagerAdded {
+			market_id: MarketId,
+			manager: T::AccountId,
+		},
+		MarketManagerRemoved {
+			market_id: MarketId,
+			manager: T::AccountId,
+		},
+		ParticipantRefunded {
+			market_id: MarketId,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		MarketPaused {
+			market_id: MarketId,
+		},
+		MarketUnpaused {
+			market_id: MarketId,
+			paused_for: T::BlockNumber,
+			end: T::BlockNumber,
+		},
 	}
 
 	#[pallet::error]
@@ -183,45 +957,155 @@ pub mod pallet {
 		InsufficientCreatorBalance,
 		OnlyMarketCreatorAllowedYet,
 		Invalid,
+		TooManyBidsInBlock,
+		BidQueueFull,
+		InvalidPayoutRatios,
+		InvalidRanking,
+		RankingRequired,
+		ReportingWindowExpired,
+		ReportingWindowNotExpired,
+		CallerNotCreator,
+		MarketPaused,
+		MarketAlreadyPaused,
+		MarketNotPaused,
+		PauseDurationExceeded,
+		InsufficientOracleBalance,
+		OracleAcceptanceExpired,
+		OracleAcceptanceNotExpired,
+		MarketNotOverdue,
+		DuplicateOutcomeData,
+		BidExpired,
+		MarketAlreadyRedeemed,
+		NothingToSettle,
+		TooManySponsors,
+		ZeroSponsorship,
+		InvalidScalarRange,
+		MarketHasBids,
+		MedianReportRequired,
+		NotScalarMarket,
+		CallerNotScalarOracle,
+		TooManyDataUpdates,
+		NothingToRefund,
+		MarketDisputed,
+		BondTopUpRequired,
+		BondSufficient,
+		OutcomeRetired,
+		OutcomeAlreadyTraded,
+		AttestationAlreadyAttached,
+		AttestationTooLong,
+		MechanismNotSupported,
+		TooManyMarketsThisBlock,
+		CallerNotOutcomeOwner,
+		TooManyManagers,
+		AlreadyManager,
+		NotManager,
+		OutcomeAlreadyOwned,
+	}
+
+	impl<T> From<CreateMarketError> for Error<T> {
+		fn from(error: CreateMarketError) -> Self {
+			match error {
+				CreateMarketError::InsufficientCreatorBalance => Error::InsufficientCreatorBalance,
+				CreateMarketError::OutcomeAmountTooLow => Error::OutcomeAmountTooLow,
+				CreateMarketError::TooManyOutcomes => Error::StorageOverflow(1u8),
+				CreateMarketError::DuplicateOutcomeData => Error::DuplicateOutcomeData,
+				CreateMarketError::InvalidPayoutRatios => Error::InvalidPayoutRatios,
+				CreateMarketError::BelowMinMarketPeriod => Error::BelowMinMarketPeriod,
+				CreateMarketError::MarketCounterOverflow => Error::StorageOverflow(0u8),
+				CreateMarketError::CloseScheduleFull => Error::StorageOverflow(2u8),
+				CreateMarketError::MechanismNotSupported => Error::MechanismNotSupported,
+				CreateMarketError::TooManyMarketsThisBlock => Error::TooManyMarketsThisBlock,
+			}
+		}
+	}
+
+	impl<T> From<BidError> for Error<T> {
+		fn from(error: BidError) -> Self {
+			match error {
+				BidError::MarketNotFound => Error::MarketNotFound,
+				BidError::MarketNotActive => Error::MarketNotActive,
+				BidError::MarketPaused => Error::MarketPaused,
+				BidError::BondTopUpRequired => Error::BondTopUpRequired,
+				BidError::InvalidOutcomeIndex => Error::InvalidOutcomeIndex,
+				BidError::OutcomeRetired => Error::OutcomeRetired,
+				BidError::PriceTooLow => Error::PriceTooLow,
+				BidError::OutcomeAlreadyOwned => Error::OutcomeAlreadyOwned,
+			}
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: T::BlockNumber) -> Weight {
-			let mut total_weight = Weight::zero();
+			let cleared = <BidsInBlock<T>>::clear(u32::MAX, None);
 
-			// TODO 8: What comes to your mind when you see the `total_weight` calculation?
-			total_weight = total_weight.saturating_add(T::DbWeight::get().reads(1));
-			let market_ids = <MarketIdsPerCloseBlock<T>>::get(n);
+			<MarketTransitionsInBlock<T>>::kill();
+			<MarketsCreatedInBlock<T>>::kill();
+
+			let queued_bids = Self::clear_queued_bids();
+
+			let market_ids =
+				<MarketIdsPerCloseBlock<T>>::drain_prefix(n).map(|(market_id, ())| market_id);
+			let market_ids = market_ids.collect::<Vec<_>>();
+			<MarketCountPerCloseBlock<T>>::remove(n);
+			<AnnouncedCloses<T>>::remove(n);
+			let closing_markets = market_ids.len() as u32;
 			for market_id in market_ids {
-				total_weight = total_weight.saturating_add(T::DbWeight::get().reads(1));
-				if let Some(mut market) = <Markets<T>>::get(market_id) {
+				if let Some(market) = <Markets<T>>::get(market_id) {
 					// TODO 9: Why could this `debug_assert!` be useful here?
-					debug_assert!(market.status == MarketStatus::Active, "MarketIdsPerCloseBlock should only contain active markets! Invalid market id: {:?}", market_id);
-					market.status = MarketStatus::Closed;
-					total_weight = total_weight.saturating_add(T::DbWeight::get().writes(1));
-					<Markets<T>>::insert(market_id, market);
-					Self::deposit_event(Event::MarketClosed { market_id });
+					debug_assert!(matches!(market.status, MarketStatus::Active | MarketStatus::PendingOracle), "MarketIdsPerCloseBlock should only contain active or pending markets! Invalid market id: {:?}", market_id);
+					// Markets without an accepted oracle can only be cancelled by the creator.
+					if market.status != MarketStatus::Active {
+						continue;
+					}
+					// Paused markets are closed, when they are unpaused or overdue.
+					if Self::is_paused(market_id) {
+						continue;
+					}
+					Self::close(market_id, market);
 				};
 			}
-			total_weight = total_weight.saturating_add(T::DbWeight::get().writes(1));
-			<MarketIdsPerCloseBlock<T>>::remove(n);
 
-			total_weight
+			let expired_markets = Self::resolve_expired_reports(n);
+
+			// `on_finalize` can't return its weight, so its worst case is reserved here.
+			T::WeightInfo::on_initialize(closing_markets, queued_bids, cleared.unique)
+				.saturating_add(T::WeightInfo::on_finalize())
+				.saturating_add(
+					T::OnParticipation::participation_weight().saturating_mul(queued_bids.into()),
+				)
+				// Resolving as invalid releases the holds of all outcomes like `redeem`.
+				.saturating_add(
+					T::WeightInfo::redeem(T::MaxOutcomes::get())
+						.saturating_add(Self::settlement_participation_weight())
+						.saturating_mul(expired_markets.into()),
+				)
 		}
 
 		fn on_finalize(n: T::BlockNumber) {
 			// TODO 10: What should be kept in mind, when using `on_finalize`?
+			Self::deposit_transitions_digest();
 			Self::on_finalize_impl(n);
 		}
 
-		fn on_idle(_n: T::BlockNumber, mut remaining_weight: Weight) -> Weight {
-			if let Some(count) = remaining_weight.checked_div(T::WeightInfo::do_something().ref_time()) {
-				let consumed_weight = Self::emit_highest_outcomes(count.ref_time() as usize);
-				remaining_weight = remaining_weight.saturating_sub(consumed_weight);
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let mut consumed_weight = Self::destroy_markets(remaining_weight);
+
+			if !T::InformationalEvents::get() {
+				return consumed_weight;
+			}
+			let remaining_weight = remaining_weight.saturating_sub(consumed_weight);
+			// Both the execution time and the proof size limit the number of events.
+			let count = remaining_weight
+				.checked_div_per_component(&T::WeightInfo::emit_highest_outcomes(1))
+				.map_or(0, |count| count.min(MaxHighestOutcomes::get().into()) as usize)
+				// Budget only for the markets, which exist.
+				.min(<Outcomes<T>>::count() as usize);
+			if !count.is_zero() {
+				consumed_weight.saturating_accrue(Self::emit_highest_outcomes(count));
 			}
 
-			remaining_weight
+			consumed_weight
 		}
 
 		fn integrity_test() {
@@ -233,45 +1117,65 @@ pub mod pallet {
 				!T::MinMarketPeriod::get().is_zero(),
 				"The minimum market period should not be zero!"
 			);
-		}
-	}
-
+			assert!(
+				!T::ReportingPeriod::get().is_zero(),
+				"The reporting period should not be zero!"
+			);
+			assert!(
+				!T::MaxBidsPerBlock::get().is_zero(),
+				"The maximum of bids per block should not be zero!"
+			);
+			assert!(
+				!T::MaxMarketsPerBlock::get().is_zero(),
+				"The maximum of markets per block should not be zero!"
+			);
+			assert!(
+				!T::CloseAnnouncementHorizon::get().is_zero(),
+				"The close announcement horizon should not be zero!"
+			);
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(T::WeightInfo::create_market(outcome_data.len() as u32))]
 		pub fn create_market(
 			origin: OriginFor<T>,
 			data: [u8; 32],
-			#[pallet::compact] outcome_amount: u8,
+			outcome_data: OutcomeDataOf<T>,
 			end: T::BlockNumber,
 			oracle: T::AccountId,
+			payout_ratios: PayoutRatiosOf<T>,
+			mechanism: Mechanism,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let bond = T::CreatorBond::get();
-			// TODO 11:: Why do we check `can_reserve` here? Why not just using `reserve` alone?
-			ensure!(T::Currency::can_reserve(&who, bond), Error::<T>::InsufficientCreatorBalance);
-
-			ensure!(!outcome_amount.is_zero(), Error::<T>::OutcomeAmountTooLow);
+			let market_id = Self::ensure_valid_market_params(
+				&who,
+				&outcome_data,
+				end,
+				&oracle,
+				&payout_ratios,
+				mechanism,
+			)
+			.map_err(Error::<T>::from)?;
+			<MarketsCreatedInBlock<T>>::mutate(|count| *count = count.saturating_add(1));
+			let new_counter = market_id.saturating_add(1);
 
+			let bond = T::CreatorBond::get();
+			let oracle_bond = T::OracleBond::get();
 			let now = <frame_system::Pallet<T>>::block_number();
-			ensure!(
-				end.saturating_sub(now) >= T::MinMarketPeriod::get(),
-				Error::<T>::BelowMinMarketPeriod
-			);
-
-			let market_id = Self::market_counter();
-			let new_counter = market_id.checked_add(1).ok_or(Error::<T>::StorageOverflow(0u8))?;
 
 			debug_assert!(!Markets::<T>::contains_key(market_id));
 
 			let mut outcomes = Outcomes::<T>::get(market_id);
-			for i in 0..outcome_amount {
-				let outcome = Outcome { owner: who.clone(), data: [i; 32], price: Zero::zero() };
+			for data in outcome_data {
+				let outcome = Outcome { owner: who.clone(), data, price: Zero::zero() };
 				outcomes.try_push(outcome).map_err(|_| Error::<T>::StorageOverflow(1u8))?;
 			}
 
+			let oracle_accepted = oracle == who;
 			let market = Market {
 				creator: who.clone(),
 				// TODO 12: Why do we like to store the bond in the market? We could have just used
@@ -281,21 +1185,45 @@ pub mod pallet {
 				end,
 				oracle,
 				oracle_outcome_report: None,
-				status: MarketStatus::Active,
+				status: if oracle_accepted {
+					MarketStatus::Active
+				} else {
+					MarketStatus::PendingOracle
+				},
 			};
 
-			MarketIdsPerCloseBlock::<T>::try_mutate(end, |prev_market_ids| -> DispatchResult {
-				prev_market_ids
-					.try_push(market_id)
-					.map_err(|_| <Error<T>>::StorageOverflow(2u8))?;
-				Ok(())
-			})?;
+			Self::schedule_close(end, market_id)?;
 
 			// TODO 13: Why could we want to reserve the bond here?
 			T::Currency::reserve(&who, bond)?;
 
+			let fee = Self::creation_fee(&who);
+			if !fee.is_zero() {
+				let imbalance = T::Currency::withdraw(
+					&who,
+					fee,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::OnCreationFee::on_unbalanced(imbalance);
+			}
+
+			if oracle_accepted {
+				T::Currency::reserve(&who, oracle_bond)?;
+				<OracleBonds<T>>::insert(market_id, oracle_bond);
+			} else {
+				let deadline = now.saturating_add(T::OracleAcceptancePeriod::get());
+				<OracleAcceptanceDeadlines<T>>::insert(market_id, deadline);
+			}
+
 			<Outcomes<T>>::insert(market_id, outcomes);
 			<Markets<T>>::insert(market_id, market);
+			if !payout_ratios.is_empty() {
+				<PayoutRatios<T>>::insert(market_id, payout_ratios);
+			}
+			if mechanism != Mechanism::default() {
+				<MarketMechanisms<T>>::insert(market_id, mechanism);
+			}
 			<MarketCounter<T>>::put(new_counter);
 
 			Self::deposit_event(Event::MarketCreated { market_id, creator: who });
@@ -306,81 +1234,136 @@ pub mod pallet {
 		// TODO 14: What does `Pays::No` mean? Why is it only placed here?
 		// TODO 15: What does `DispatchClass::Operational` mean? Why is it only placed here?
 		#[pallet::call_index(1)]
-		#[pallet::weight((T::WeightInfo::do_something(), DispatchClass::Operational, Pays::No))]
+		#[pallet::weight((
+			T::WeightInfo::destroy_market(T::MaxOutcomes::get()),
+			DispatchClass::Operational,
+			Pays::No,
+		))]
 		pub fn destroy_market(
 			origin: OriginFor<T>,
 			#[pallet::compact] market_id: MarketId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			// TODO 16: Why didn't I use `ensure_root(origin)?;` here?
 			T::DestroyOrigin::ensure_origin(origin)?;
 
-			ensure!(Markets::<T>::contains_key(market_id), Error::<T>::MarketNotFound);
-
-			Markets::<T>::remove(market_id);
-			Outcomes::<T>::remove(market_id);
+			let mut market = Markets::<T>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status != MarketStatus::Disputed, Error::<T>::MarketDisputed);
+			ensure!(market.status != MarketStatus::Destroying, Error::<T>::InvalidMarketStatus);
+			let outcome_amount = Outcomes::<T>::decode_len(market_id).unwrap_or(0) as u32;
+
+			Self::unschedule_close(market.end, market_id);
+			Self::unschedule_report_deadline(market.end, market_id);
+			Self::release_oracle_bond(market_id, &market.oracle);
+			Self::refund_sponsors(market_id);
+			Self::release_scalar_reports(market_id);
+			Self::release_standing_orders(market_id, &<Outcomes<T>>::get(market_id));
+			T::Currency::unreserve(&market.creator, market.bond);
+			// The holds of unresolved markets are returned to the outcome owners.
+			if !matches!(market.status, MarketStatus::Redeemed | MarketStatus::Invalid) {
+				let reserve_id = HoldReason::OutcomeBid.identifier();
+				for outcome in Self::closed_outcomes(market_id).iter() {
+					if !outcome.price.is_zero() {
+						T::Currency::unreserve_named(&reserve_id, &outcome.owner, outcome.price);
+					}
+				}
+			}
 
-			Self::deposit_event(Event::MarketDestroyed { market_id });
+			Self::deposit_removal_receipt(
+				market_id,
+				market.clone(),
+				BondDisposition::ReturnedToCreator,
+			);
+			// The storage is removed in bounded steps, `MarketDestroyed` is emitted at the end.
+			market.status = MarketStatus::Destroying;
+			<Markets<T>>::insert(market_id, market);
+			<MarketsToDestroy<T>>::insert(market_id, ());
 
-			Ok(())
+			Ok(Some(T::WeightInfo::destroy_market(outcome_amount)).into())
 		}
 
 		// TODO 17: What could be done instead of `Pays::Yes` to get the same effect?
 		// TODO 18: What does `DispatchClass::Normal` mean?
 		// TODO 19: Why could this `transactional` be useful here? Why is not used in other calls?
 		#[pallet::call_index(2)]
-		#[pallet::weight((T::WeightInfo::do_something(), DispatchClass::Normal, Pays::Yes))]
+		#[pallet::weight((
+			T::WeightInfo::buy_outcome().saturating_add(T::OnParticipation::participation_weight()),
+			DispatchClass::Normal,
+			Pays::Yes,
+		))]
 		#[frame_support::transactional]
 		pub fn buy_outcome(
 			origin: OriginFor<T>,
 			#[pallet::compact] market_id: MarketId,
 			#[pallet::compact] outcome_index: u8,
 			#[pallet::compact] price: BalanceOf<T>,
+			valid_until: T::BlockNumber,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			// Reject bids, which were delayed in the transaction pool, before any other check.
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= valid_until, Error::<T>::BidExpired);
+
+			<BidsInBlock<T>>::try_mutate(&who, |bids| -> DispatchResult {
+				*bids = bids.saturating_add(1);
+				ensure!(*bids <= T::MaxBidsPerBlock::get(), Error::<T>::TooManyBidsInBlock);
+				Ok(())
+			})?;
+
 			let buyer_balance = T::Currency::free_balance(&who);
 			let new_buyer_balance =
 				buyer_balance.checked_sub(&price).ok_or(Error::<T>::InsufficientBuyerBalance)?;
 			T::Currency::ensure_can_withdraw(
 				&who,
 				price,
-				WithdrawReasons::TRANSFER,
+				WithdrawReasons::RESERVE,
 				new_buyer_balance,
 			)?;
 
-			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
-			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
-
-			let mut outcomes = Outcomes::<T>::get(market_id);
-			let outcome = outcomes
-				.get_mut(outcome_index as usize)
-				.ok_or(Error::<T>::InvalidOutcomeIndex)?;
-			ensure!(outcome.price < price, Error::<T>::PriceTooLow);
+			let (_, mut outcomes) = Self::ensure_valid_bid(market_id, outcome_index, price)
+				.map_err(Error::<T>::from)?;
 
-			let market_account = Self::market_account(market_id);
+			let reserve_id = HoldReason::OutcomeBid.identifier();
 
-			let refund_previous_buyer = || -> DispatchResult {
-				let previous_buyer = &outcome.owner;
-				T::Currency::transfer(
-					&market_account,
-					&previous_buyer,
-					outcome.price,
-					ExistenceRequirement::AllowDeath,
-				)?;
-				Ok(())
-			};
+			Self::note_traded(market_id, outcome_index)?;
 
-			if !outcome.price.is_zero() {
-				refund_previous_buyer()?;
+			if T::BatchBidClearing::get() {
+				T::Currency::reserve_named(&reserve_id, &who, price)?;
+				let bid = QueuedBid { market_id, outcome_index, buyer: who.clone(), price };
+				<QueuedBids<T>>::try_append(bid).map_err(|_| Error::<T>::BidQueueFull)?;
+				Self::deposit_event(Event::BidQueued {
+					market_id,
+					outcome_index,
+					buyer: who,
+					price,
+				});
+				return Ok(());
 			}
 
-			T::Currency::transfer(&who, &market_account, price, ExistenceRequirement::AllowDeath)?;
+			if Self::rebid_standing_order(market_id, &mut outcomes, outcome_index, &who, price)?
+				.is_some()
+			{
+				<Outcomes<T>>::insert(market_id, outcomes);
+				return Ok(());
+			}
 
-			outcome.owner = who.clone();
-			outcome.price = price;
+			let previous_owner = <MarketMechanisms<T>>::get(market_id).trade::<T>(
+				market_id,
+				&mut outcomes,
+				outcome_index,
+				&who,
+				price,
+			)?;
+			T::Currency::reserve_named(&reserve_id, &who, price)?;
 
+			Self::index_position(market_id, &outcomes, &previous_owner, &who);
 			<Outcomes<T>>::insert(market_id, outcomes);
+			Self::log_bid(
+				market_id,
+				BidRecord { buyer: who.clone(), outcome_index, price, block: now },
+			);
 
+			T::OnParticipation::participation_recorded(&who, market_id, Participation::Bid, price);
 			Self::deposit_event(Event::OutcomeBought { market_id, outcome_index, buyer: who });
 
 			Ok(())
@@ -388,7 +1371,7 @@ pub mod pallet {
 
 		// TODO 20: What could the users do, if the oracle is not honest? What is done at Zeitgeist to solve this problem?
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(T::WeightInfo::report_as_oracle())]
 		pub fn report_as_oracle(
 			origin: OriginFor<T>,
 			#[pallet::compact] market_id: MarketId,
@@ -401,10 +1384,19 @@ pub mod pallet {
 			ensure!(market.oracle_outcome_report.is_none(), Error::<T>::OutcomeAlreadyReported);
 			ensure!(market.status == MarketStatus::Closed, Error::<T>::InvalidMarketStatus);
 			ensure!(market.oracle == who, Error::<T>::CallerNotOracle);
+			Self::ensure_reporting_window_open(&market)?;
+			ensure!(!<ScalarOracles<T>>::contains_key(market_id), Error::<T>::MedianReportRequired);
+			ensure!(
+				<PayoutRatios<T>>::decode_len(market_id).unwrap_or(0) <= 1,
+				Error::<T>::RankingRequired
+			);
+			ensure!(!Self::is_retired(market_id, outcome_index), Error::<T>::OutcomeRetired);
 
 			market.oracle_outcome_report = Some(outcome_index);
 			market.status = MarketStatus::Reported;
+			Self::release_oracle_bond(market_id, &market.oracle);
 			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.reported);
 
 			Self::deposit_event(Event::MarketReported {
 				market_id,
@@ -415,36 +1407,75 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(
+			T::WeightInfo::redeem(T::MaxOutcomes::get())
+				.saturating_add(Pallet::<T>::settlement_participation_weight())
+		)]
 		pub fn redeem(
 			origin: OriginFor<T>,
 			#[pallet::compact] market_id: MarketId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 
 			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status != MarketStatus::Redeemed, Error::<T>::MarketAlreadyRedeemed);
+			ensure!(market.status != MarketStatus::Disputed, Error::<T>::MarketDisputed);
 
 			let reported_index =
 				market.oracle_outcome_report.ok_or(Error::<T>::OutcomeNotReportedYet)?;
-			debug_assert!(market.status == MarketStatus::Reported);
+			ensure!(market.status == MarketStatus::Reported, Error::<T>::InvalidMarketStatus);
 
-			let outcomes = <Outcomes<T>>::get(market_id);
+			let outcomes = Self::closed_outcomes(market_id);
 			let outcome =
 				outcomes.get(reported_index as usize).ok_or(Error::<T>::InvalidOutcomeIndex)?;
 
 			let winner = &outcome.owner;
 
 			let market_account = Self::market_account(market_id);
-			let reward = T::Currency::free_balance(&market_account);
+			let mut flows = BTreeMap::<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>::new();
+			<MarketMechanisms<T>>::get(market_id).collect::<T>(market_id, &outcomes, &mut flows)?;
+			let pot = T::Currency::free_balance(&market_account);
+			let reward = Self::pay_fees(market_id, &market, pot, &mut flows)?;
+
+			// The runner-ups get their share first, the winner gets the rest including dust.
+			let mut winner_reward = reward;
+			let ranking = <RankedReports<T>>::get(market_id);
+			let payout_ratios = <PayoutRatios<T>>::get(market_id);
+			for (outcome_index, ratio) in ranking.iter().zip(payout_ratios.iter()).skip(1) {
+				let position =
+					outcomes.get(*outcome_index as usize).ok_or(Error::<T>::InvalidOutcomeIndex)?;
+				let amount = ratio.mul_floor(reward);
+				T::Currency::transfer(
+					&market_account,
+					&position.owner,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				winner_reward = winner_reward.saturating_sub(amount);
+				let (_, paid_out) = flows.entry(position.owner.clone()).or_default();
+				paid_out.saturating_accrue(amount);
+				Self::deposit_event(Event::PositionPaidOut {
+					market_id,
+					outcome_index: *outcome_index,
+					owner: position.owner.clone(),
+					amount,
+				});
+			}
+
 			T::Currency::transfer(
 				&market_account,
 				winner,
-				reward,
+				winner_reward,
 				ExistenceRequirement::AllowDeath,
 			)?;
+			let (_, paid_out) = flows.entry(winner.clone()).or_default();
+			paid_out.saturating_accrue(winner_reward);
+			Self::store_settlement_report(market_id, flows)?;
 
+			Self::unindex_positions(market_id, &outcomes);
 			market.status = MarketStatus::Redeemed;
 			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.redeemed);
 
 			Self::deposit_event(Event::MarketRedeemed {
 				market_id,
@@ -452,27 +1483,40 @@ pub mod pallet {
 				winner: winner.clone(),
 			});
 
-			Ok(())
+			Ok(Some(
+				T::WeightInfo::redeem(outcomes.len() as u32)
+					.saturating_add(Self::settlement_participation_weight()),
+			)
+			.into())
 		}
 
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(
+			T::WeightInfo::clear_storage_repatriate().max(T::WeightInfo::clear_storage_unreserve())
+		)]
 		pub fn clear_storage(
 			origin: OriginFor<T>,
 			#[pallet::compact] market_id: MarketId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
-			ensure!(market.status == MarketStatus::Redeemed, Error::<T>::InvalidMarketStatus);
+			ensure!(
+				matches!(market.status, MarketStatus::Redeemed | MarketStatus::Invalid),
+				Error::<T>::InvalidMarketStatus
+			);
 
 			let now = <frame_system::Pallet<T>>::block_number();
 			let end = market.end;
 			if now.saturating_sub(end) <= T::MarketCreatorClearStorageTime::get() {
-				ensure!(market.creator == who, Error::<T>::OnlyMarketCreatorAllowedYet);
+				ensure!(
+					Self::is_manager(market_id, &market, &who),
+					Error::<T>::OnlyMarketCreatorAllowedYet
+				);
 			}
 
-			if who != market.creator {
+			// The bond goes back to the creator, if a manager clears the storage.
+			let (actual_weight, bond) = if !Self::is_manager(market_id, &market, &who) {
 				// TODO 21: Why don't I use a question mark operator here?
 				let res = T::Currency::repatriate_reserved(
 					&market.creator,
@@ -481,91 +1525,2288 @@ pub mod pallet {
 					BalanceStatus::Free,
 				);
 				debug_assert!(res.is_ok());
+				(T::WeightInfo::clear_storage_repatriate(), BondDisposition::PaidTo(who))
 			} else {
 				T::Currency::unreserve(&market.creator, market.bond);
+				(T::WeightInfo::clear_storage_unreserve(), BondDisposition::ReturnedToCreator)
+			};
+
+			Self::deposit_removal_receipt(market_id, market, bond);
+			Self::remove_market(market_id);
+
+			Ok(Some(actual_weight).into())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::report_ranking_as_oracle(ranking.len() as u32))]
+		pub fn report_ranking_as_oracle(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			ranking: RankingOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+
+			ensure!(market.oracle_outcome_report.is_none(), Error::<T>::OutcomeAlreadyReported);
+			ensure!(market.status == MarketStatus::Closed, Error::<T>::InvalidMarketStatus);
+			ensure!(market.oracle == who, Error::<T>::CallerNotOracle);
+			Self::ensure_reporting_window_open(&market)?;
+			ensure!(!<ScalarOracles<T>>::contains_key(market_id), Error::<T>::MedianReportRequired);
+
+			let payout_ratios = <PayoutRatios<T>>::get(market_id);
+			ensure!(ranking.len() == payout_ratios.len().max(1), Error::<T>::InvalidRanking);
+			let outcome_amount = <Outcomes<T>>::decode_len(market_id).unwrap_or(0);
+			for (i, outcome_index) in ranking.iter().enumerate() {
+				ensure!(
+					(*outcome_index as usize) < outcome_amount,
+					Error::<T>::InvalidOutcomeIndex
+				);
+				ensure!(!ranking[..i].contains(outcome_index), Error::<T>::InvalidRanking);
+				ensure!(!Self::is_retired(market_id, *outcome_index), Error::<T>::OutcomeRetired);
 			}
 
-			<Markets<T>>::remove(market_id);
-			<Outcomes<T>>::remove(market_id);
+			let winner_outcome = ranking[0];
+			market.oracle_outcome_report = Some(winner_outcome);
+			market.status = MarketStatus::Reported;
+			Self::release_oracle_bond(market_id, &market.oracle);
+			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.reported);
+			<RankedReports<T>>::insert(market_id, &ranking);
+
+			Self::deposit_event(Event::MarketRankingReported { market_id, ranking });
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn on_finalize_impl(n: T::BlockNumber) {
-			let next_block = n.saturating_add(One::one());
-			let market_ids_to_close_next_block = <MarketIdsPerCloseBlock<T>>::get(next_block);
-			if market_ids_to_close_next_block.is_empty() {
-				return;
-			}
-			Self::deposit_event(Event::MarketsToCloseNextBlock {
-				market_ids: market_ids_to_close_next_block.into_inner(),
-			});
+		#[pallet::call_index(7)]
+		#[pallet::weight(
+			T::WeightInfo::resolve_expired_market_invalid(T::MaxOutcomes::get())
+				.max(T::WeightInfo::resolve_expired_market_scalar(T::MaxScalarOracles::get()))
+				.saturating_add(Pallet::<T>::settlement_participation_weight())
+		)]
+		#[frame_support::transactional]
+		pub fn resolve_expired_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Closed, Error::<T>::InvalidMarketStatus);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now > Self::reporting_deadline(market.end),
+				Error::<T>::ReportingWindowNotExpired
+			);
+
+			Self::resolve_expired(market_id, market)
 		}
 
-		// TODO 22: What could be the purpose of this function?
-		pub fn g(o: OutcomesOf<T>, i: u8) -> Result<BalanceOf<T>, DispatchError> {
-			use frame_support::sp_runtime::SaturatedConversion;
-			let t = o
-				.iter()
-				.map(|j| j.price.saturated_into::<u128>())
-				.sum::<u128>()
-				.saturated_into::<BalanceOf<T>>();
-			let u = o.get(i as usize).ok_or(Error::<T>::Invalid)?;
-			Ok(u.p(t))
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::accept_oracle_role())]
+		pub fn accept_oracle_role(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::PendingOracle, Error::<T>::InvalidMarketStatus);
+			ensure!(market.oracle == who, Error::<T>::CallerNotOracle);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let deadline = <OracleAcceptanceDeadlines<T>>::get(market_id).unwrap_or(now);
+			ensure!(now <= deadline && now < market.end, Error::<T>::OracleAcceptanceExpired);
+
+			let oracle_bond = T::OracleBond::get();
+			ensure!(
+				T::Currency::can_reserve(&who, oracle_bond),
+				Error::<T>::InsufficientOracleBalance
+			);
+			T::Currency::reserve(&who, oracle_bond)?;
+
+			market.status = MarketStatus::Active;
+			<Markets<T>>::insert(market_id, market);
+			<OracleBonds<T>>::insert(market_id, oracle_bond);
+			<OracleAcceptanceDeadlines<T>>::remove(market_id);
+
+			Self::deposit_event(Event::OracleAccepted { market_id, oracle: who });
+
+			Ok(())
 		}
 
-		pub fn emit_highest_outcomes(count: usize) -> Weight {
-			let mut total_weight = Weight::zero();
-			for (market_id, outcomes) in <Outcomes<T>>::iter().take(count) {
-				let highest_outcome = outcomes
-					.iter()
-					.enumerate()
-					.max_by_key(|(_, outcome)| outcome.price)
-					.map(|(index, _)| index as u8);
-				Self::deposit_event(Event::HighestOutcome { market_id, highest_outcome });
+		/// Designate a new oracle, if the current oracle didn't accept the oracle role in time.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::replace_oracle())]
+		pub fn replace_oracle(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			oracle: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-				total_weight = total_weight.saturating_add(T::WeightInfo::do_something());
-			}
-			total_weight
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::PendingOracle, Error::<T>::InvalidMarketStatus);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let deadline = <OracleAcceptanceDeadlines<T>>::get(market_id).unwrap_or(now);
+			ensure!(now > deadline, Error::<T>::OracleAcceptanceNotExpired);
+
+			market.oracle = oracle.clone();
+			<Markets<T>>::insert(market_id, market);
+			<OracleAcceptanceDeadlines<T>>::insert(
+				market_id,
+				now.saturating_add(T::OracleAcceptancePeriod::get()),
+			);
+
+			Self::deposit_event(Event::OracleReplaced { market_id, oracle });
+
+			Ok(())
 		}
 
-		pub fn market_account(market_id: MarketId) -> AccountIdOf<T> {
-			use frame_support::sp_runtime::traits::AccountIdConversion;
-			T::PalletId::get().into_sub_account_truncating(market_id)
+		/// Cancel a market, which has no accepted oracle yet, and return the creator bond.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::cancel_market())]
+		pub fn cancel_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::PendingOracle, Error::<T>::InvalidMarketStatus);
+
+			T::Currency::unreserve(&market.creator, market.bond);
+			Self::unschedule_close(market.end, market_id);
+			Self::deposit_removal_receipt(market_id, market, BondDisposition::ReturnedToCreator);
+			Self::remove_market(market_id);
+
+			Self::deposit_event(Event::MarketCancelled { market_id });
+
+			Ok(())
 		}
-	}
 
-	impl<T: Config> MarketApi for Pallet<T>
-	{
-		type MarketId = MarketId;
-		type AccountId = T::AccountId;
-		type Balance = BalanceOf<T>;
-		type BlockNumber = T::BlockNumber;
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::pause_market())]
+		pub fn pause_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		fn get_market(market_id: &Self::MarketId) -> Result<(Weight, MarketOf<T>), DispatchError> {
-			let weight = T::DbWeight::get().reads(1);
 			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
-			Ok((weight, market))
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			<MarketPauses<T>>::try_mutate(market_id, |pause| -> DispatchResult {
+				ensure!(pause.paused_since.is_none(), Error::<T>::MarketAlreadyPaused);
+				ensure!(
+					pause.total_paused < T::MaxPauseDuration::get(),
+					Error::<T>::PauseDurationExceeded
+				);
+				pause.paused_since = Some(now);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MarketPaused { market_id });
+
+			Ok(())
 		}
-	}
-}
 
-// TODO 23: Imagine this trait is defined outside of this pallet. Why could this be useful?
-trait MarketApi {
-	type MarketId;
-	type AccountId;
-	type Balance;
-	type BlockNumber;
+		/// Resume the trading of a paused market. If `extend_end` is set, the market end is
+		/// postponed by the paused duration. A market, whose end passed while it was paused, is
+		/// closed.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::unpause_market())]
+		pub fn unpause_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			extend_end: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-	fn get_market(
-		market_id: &Self::MarketId,
-	) -> Result<
-		(
-			frame_support::pallet_prelude::Weight,
-			Market<Self::AccountId, Self::BlockNumber, Self::Balance>,
-		),
-		frame_support::pallet_prelude::DispatchError,
-	>;
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			let mut pause = <MarketPauses<T>>::get(market_id);
+			let paused_since = pause.paused_since.take().ok_or(Error::<T>::MarketNotPaused)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let allowance = T::MaxPauseDuration::get().saturating_sub(pause.total_paused);
+			let paused_for = now.saturating_sub(paused_since).min(allowance);
+			pause.total_paused = pause.total_paused.saturating_add(paused_for);
+
+			let end = market.end;
+			if extend_end && !paused_for.is_zero() {
+				market.end = market.end.saturating_add(paused_for);
+			}
+
+			<MarketPauses<T>>::insert(market_id, pause);
+
+			Self::deposit_event(Event::MarketUnpaused { market_id, paused_for, end: market.end });
+
+			// The close of a market, which ended while it was paused, was skipped.
+			if market.end <= now {
+				Self::close(market_id, market);
+			} else if market.end != end {
+				Self::schedule_close(market.end, market_id)?;
+				Self::unschedule_close(end, market_id);
+				<Markets<T>>::insert(market_id, &market);
+			}
+
+			Ok(())
+		}
+
+		/// Close an active market, whose end is in the past, but which wasn't closed in
+		/// `on_initialize`. The caller receives the keeper tip from the creator bond.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::close_market())]
+		pub fn close_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+			ensure!(!Self::is_paused(market_id), Error::<T>::MarketPaused);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(market.end < now, Error::<T>::MarketNotOverdue);
+
+			Self::unschedule_close(market.end, market_id);
+
+			let tip = T::FeeSchedule::keeper_tip().min(market.bond);
+			let missing =
+				T::Currency::repatriate_reserved(&market.creator, &who, tip, BalanceStatus::Free)?;
+			let amount = tip.saturating_sub(missing);
+			market.bond = market.bond.saturating_sub(amount);
+
+			Self::close(market_id, market);
+			Self::deposit_event(Event::KeeperTipPaid { market_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Perform the pending terminal step of each market: close it if it's overdue, resolve
+		/// it as invalid if the reporting window expired or clear its storage if the creator
+		/// period to clear it passed. Markets without a pending step are skipped.
+		#[pallet::call_index(14)]
+		#[pallet::weight(
+			T::WeightInfo::close_market()
+				.max(T::WeightInfo::resolve_expired_market_invalid(T::MaxOutcomes::get()))
+				.max(T::WeightInfo::resolve_expired_market_scalar(T::MaxScalarOracles::get()))
+				.max(T::WeightInfo::clear_storage_repatriate())
+				.max(T::WeightInfo::clear_storage_unreserve())
+				.saturating_add(Pallet::<T>::settlement_participation_weight())
+				.saturating_mul(market_ids.len() as u64)
+		)]
+		pub fn settle_markets(
+			origin: OriginFor<T>,
+			market_ids: BoundedVec<MarketId, SettlementBatchSize>,
+		) -> DispatchResult {
+			ensure_signed(origin.clone())?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut settled = 0u32;
+			for market_id in market_ids {
+				let market = match <Markets<T>>::get(market_id) {
+					Some(market) => market,
+					None => continue,
+				};
+				let origin = origin.clone();
+				let res = with_storage_layer(|| match market.status {
+					MarketStatus::Active if market.end < now => {
+						Self::close_market(origin, market_id)
+					},
+					MarketStatus::Closed if now > Self::reporting_deadline(market.end) => {
+						Self::resolve_expired_market(origin, market_id)
+					},
+					MarketStatus::Redeemed | MarketStatus::Invalid
+						if now.saturating_sub(market.end)
+							> T::MarketCreatorClearStorageTime::get() =>
+					{
+						Self::clear_storage(origin, market_id).map(|_| ()).map_err(|e| e.error)
+					},
+					_ => Err(Error::<T>::NothingToSettle.into()),
+				});
+				if res.is_ok() {
+					settled = settled.saturating_add(1);
+				}
+			}
+
+			Self::deposit_event(Event::MarketsSettled { settled });
+
+			Ok(())
+		}
+
+		/// Turn an active market without bids into a scalar market, whose outcomes are equally
+		/// sized buckets of `lower..upper`. The market resolves to the bucket of the median of the
+		/// values reported by the `oracles` in the reporting window.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::configure_scalar_market())]
+		pub fn configure_scalar_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			oracles: ScalarOraclesOf<T>,
+			lower: u128,
+			upper: u128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+			let outcomes = <Outcomes<T>>::get(market_id);
+			ensure!(
+				outcomes.iter().all(|outcome| outcome.price.is_zero()),
+				Error::<T>::MarketHasBids
+			);
+			// Every bucket has a width of at least one.
+			let width = upper.saturating_sub(lower);
+			ensure!(width >= outcomes.len() as u128, Error::<T>::InvalidScalarRange);
+			ensure!(!oracles.is_empty(), Error::<T>::InvalidScalarRange);
+
+			<ScalarOracles<T>>::insert(market_id, oracles);
+			<ScalarRanges<T>>::insert(market_id, (lower, upper));
+
+			Self::deposit_event(Event::ScalarMarketConfigured { market_id, lower, upper });
+
+			Ok(())
+		}
+
+		/// Report the value of a closed scalar market as one of its oracles. The `OracleBond` is
+		/// held until the market is resolved and slashed if the value is an outlier.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::report_scalar())]
+		pub fn report_scalar(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			value: u128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Closed, Error::<T>::InvalidMarketStatus);
+			Self::ensure_reporting_window_open(&market)?;
+			let oracles = <ScalarOracles<T>>::get(market_id).ok_or(Error::<T>::NotScalarMarket)?;
+			ensure!(oracles.contains(&who), Error::<T>::CallerNotScalarOracle);
+
+			<ScalarReports<T>>::try_mutate(market_id, |reports| -> DispatchResult {
+				ensure!(
+					!reports.iter().any(|(oracle, _)| *oracle == who),
+					Error::<T>::OutcomeAlreadyReported
+				);
+				reports
+					.try_push((who.clone(), value))
+					.map_err(|_| Error::<T>::StorageOverflow(3u8))?;
+				Ok(())
+			})?;
+			T::Currency::reserve(&who, T::OracleBond::get())?;
+
+			Self::deposit_event(Event::ScalarValueReported { market_id, oracle: who, value });
+
+			Ok(())
+		}
+
+		/// Add funds to the pot of an unresolved market, which is paid out to the winners on
+		/// redemption or returned to the sponsors if the market is resolved as invalid.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::sponsor_market())]
+		#[frame_support::transactional]
+		pub fn sponsor_market(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroSponsorship);
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(
+				matches!(market.status, MarketStatus::Active | MarketStatus::Closed),
+				Error::<T>::InvalidMarketStatus
+			);
+
+			<MarketSponsors<T>>::try_mutate(market_id, |sponsors| -> DispatchResult {
+				match sponsors.iter_mut().find(|(sponsor, _)| *sponsor == who) {
+					Some((_, total)) => total.saturating_accrue(amount),
+					None => sponsors
+						.try_push((who.clone(), amount))
+						.map_err(|_| Error::<T>::TooManySponsors)?,
+				}
+				Ok(())
+			})?;
+			T::Currency::transfer(
+				&who,
+				&Self::market_account(market_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::MarketSponsored { market_id, sponsor: who, amount });
+
+			Ok(())
+		}
+
+		/// Update the data of an active market to clarify the question. Updates are free until
+		/// the first bid. Afterwards only `MaxDataUpdates` updates are allowed and each requires
+		/// an increasing deposit, which is added to the creator bond. Only the creator can make
+		/// these updates, because the bond is returned to the creator.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::update_market_data())]
+		pub fn update_market_data(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			data: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			let trading_started =
+				<Outcomes<T>>::get(market_id).iter().any(|outcome| !outcome.price.is_zero());
+			let deposit = if trading_started {
+				ensure!(market.creator == who, Error::<T>::CallerNotCreator);
+				let updates = <DataUpdates<T>>::get(market_id).saturating_add(1);
+				ensure!(updates <= T::MaxDataUpdates::get(), Error::<T>::TooManyDataUpdates);
+				let deposit = T::DataUpdateDeposit::get().saturating_mul(updates.into());
+				T::Currency::reserve(&market.creator, deposit)?;
+				<DataUpdates<T>>::insert(market_id, updates);
+				deposit
+			} else {
+				Zero::zero()
+			};
+
+			market.data = data;
+			market.bond.saturating_accrue(deposit);
+			<Markets<T>>::insert(market_id, market);
+
+			Self::deposit_event(Event::MarketDataUpdated { market_id, data, deposit });
+
+			Ok(())
+		}
+
+		/// Set or remove the locale of an active market, which indexes the market by its region.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_market_locale())]
+		pub fn set_market_locale(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			locale: Option<LocaleTag>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Self::ensure_manager(market_id, &market, &who)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			Self::unindex_locale(market_id);
+			if let Some(locale) = locale {
+				<MarketLocales<T>>::insert(market_id, locale);
+				<MarketsByRegion<T>>::insert(locale.region, market_id, ());
+			}
+
+			Self::deposit_event(Event::MarketLocaleSet { market_id, locale });
+
+			Ok(())
+		}
+
+		/// Return the held and queued bids of the participant in an active market. The outcomes of
+		/// the participant go back to the creator without a price, so they leave the market pot,
+		/// and the bids of the participant are removed from the bid log.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::force_refund_participant())]
+		pub fn force_refund_participant(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			who: T::AccountId,
+		) -> DispatchResult {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			// The outcomes are frozen in the snapshot at the close.
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			let reserve_id = HoldReason::OutcomeBid.identifier();
+			let mut outcomes = <Outcomes<T>>::get(market_id);
+			let mut amount = BalanceOf::<T>::zero();
+			for (outcome_index, outcome) in outcomes.iter_mut().enumerate() {
+				if outcome.owner != who || outcome.price.is_zero() {
+					continue;
+				}
+				Self::release_standing_order(market_id, outcome_index as u8, outcome);
+				let missing = T::Currency::unreserve_named(&reserve_id, &who, outcome.price);
+				debug_assert!(missing.is_zero());
+				amount.saturating_accrue(outcome.price.saturating_sub(missing));
+				outcome.owner = market.creator.clone();
+				outcome.price = Zero::zero();
+			}
+			// The queued bids of the participant can't win at the next clearing anymore.
+			<QueuedBids<T>>::mutate(|queued_bids| {
+				queued_bids.retain(|bid| {
+					if bid.market_id != market_id || bid.buyer != who {
+						return true;
+					}
+					let missing = T::Currency::unreserve_named(&reserve_id, &who, bid.price);
+					debug_assert!(missing.is_zero());
+					amount.saturating_accrue(bid.price.saturating_sub(missing));
+					false
+				});
+			});
+			ensure!(!amount.is_zero(), Error::<T>::NothingToRefund);
+
+			<Outcomes<T>>::insert(market_id, outcomes);
+			<AccountPositions<T>>::remove(&who, market_id);
+			// The refunded bids don't count for the price history anymore.
+			<BidLogs<T>>::mutate(market_id, |log| log.retain(|record| record.buyer != who));
+
+			Self::deposit_event(Event::ParticipantRefunded { market_id, who, amount });
+
+			Ok(())
+		}
+
+		/// Top up the creator bond of a market to the bond required by governance or, without a
+		/// requirement, to the current `CreatorBond`.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::top_up_bond())]
+		pub fn top_up_bond(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.creator == who, Error::<T>::CallerNotCreator);
+			ensure!(
+				matches!(
+					market.status,
+					MarketStatus::Active | MarketStatus::Closed | MarketStatus::Reported
+				),
+				Error::<T>::InvalidMarketStatus
+			);
+
+			let required = <RequiredBonds<T>>::get(market_id).unwrap_or_else(T::CreatorBond::get);
+			let amount = required.saturating_sub(market.bond);
+			ensure!(!amount.is_zero(), Error::<T>::BondSufficient);
+			T::Currency::reserve(&who, amount)?;
+
+			market.bond = required;
+			<Markets<T>>::insert(market_id, market);
+			<RequiredBonds<T>>::remove(market_id);
+
+			Self::deposit_event(Event::BondToppedUp { market_id, amount });
+
+			Ok(())
+		}
+
+		/// Require the active markets, whose pot exceeds `pot_threshold`, to top up their creator
+		/// bond to the current `CreatorBond`. The markets don't accept bids until the top-up.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::require_bond_top_up(market_ids.len() as u32))]
+		pub fn require_bond_top_up(
+			origin: OriginFor<T>,
+			market_ids: BoundedVec<MarketId, SettlementBatchSize>,
+			pot_threshold: BalanceOf<T>,
+		) -> DispatchResult {
+			T::BondTopUpOrigin::ensure_origin(origin)?;
+
+			let required = T::CreatorBond::get();
+			for market_id in market_ids {
+				let market = match <Markets<T>>::get(market_id) {
+					Some(market) if market.status == MarketStatus::Active => market,
+					_ => continue,
+				};
+				let pot = Self::market_pot(market_id, &<Outcomes<T>>::get(market_id));
+				if market.bond >= required || pot <= pot_threshold {
+					continue;
+				}
+				<RequiredBonds<T>>::insert(market_id, required);
+				Self::deposit_event(Event::BondTopUpRequired { market_id, required });
+			}
+
+			Ok(())
+		}
+
+		/// Retire an outcome of an active market, which became impossible. The outcome can't be
+		/// bought or reported afterwards. The creator can only retire outcomes without a bid, the
+		/// `ModerationOrigin` refunds the bid of the owner.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::retire_outcome())]
+		pub fn retire_outcome(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			#[pallet::compact] outcome_index: u8,
+		) -> DispatchResult {
+			let caller = match T::ModerationOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			if let Some(who) = &caller {
+				Self::ensure_manager(market_id, &market, who)?;
+			}
+			// The outcomes are frozen in the snapshot at the close.
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+
+			let mut outcomes = <Outcomes<T>>::get(market_id);
+			let outcome = outcomes
+				.get_mut(outcome_index as usize)
+				.ok_or(Error::<T>::InvalidOutcomeIndex)?;
+			ensure!(!Self::is_retired(market_id, outcome_index), Error::<T>::OutcomeRetired);
+			ensure!(
+				caller.is_none() || !Self::is_traded(market_id, outcome_index),
+				Error::<T>::OutcomeAlreadyTraded
+			);
+
+			let mut refund = None;
+			Self::release_standing_order(market_id, outcome_index, outcome);
+			if !outcome.price.is_zero() {
+				let missing = T::Currency::unreserve_named(
+					&HoldReason::OutcomeBid.identifier(),
+					&outcome.owner,
+					outcome.price,
+				);
+				debug_assert!(missing.is_zero());
+				let previous_owner = core::mem::replace(&mut outcome.owner, market.creator.clone());
+				refund = Some((previous_owner, core::mem::take(&mut outcome.price)));
+			}
+			if let Some((previous_owner, _)) = &refund {
+				let still_owns = outcomes
+					.iter()
+					.any(|outcome| outcome.owner == *previous_owner && !outcome.price.is_zero());
+				if !still_owns {
+					<AccountPositions<T>>::remove(previous_owner, market_id);
+				}
+			}
+			<Outcomes<T>>::insert(market_id, outcomes);
+			<RetiredOutcomes<T>>::try_mutate(market_id, |retired| retired.try_push(outcome_index))
+				.map_err(|_| Error::<T>::InvalidOutcomeIndex)?;
+
+			Self::deposit_event(Event::OutcomeRetired { market_id, outcome_index, refund });
+
+			Ok(())
+		}
+
+		/// Attach an attestation to the report of the oracle, once the market is resolved by it.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::attest_resolution())]
+		pub fn attest_resolution(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			attestation: AttestationOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.oracle == who, Error::<T>::CallerNotOracle);
+			// An invalid or disputed market isn't resolved by the report of the oracle.
+			ensure!(
+				matches!(market.status, MarketStatus::Reported | MarketStatus::Redeemed),
+				Error::<T>::InvalidMarketStatus
+			);
+
+			Self::store_attestation(market_id, who, attestation)
+		}
+
+		/// Register a maximum price for an owned outcome, up to which the outcome is rebid by
+		/// `BidIncrement` over each higher bid. The difference to the current price is held
+		/// upfront. `None` cancels the standing order.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::set_max_bid())]
+		pub fn set_max_bid(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			#[pallet::compact] outcome_index: u8,
+			max_price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+			// Standing orders only rebid over higher bids.
+			ensure!(
+				<MarketMechanisms<T>>::get(market_id).allows_outbid(),
+				Error::<T>::MechanismNotSupported
+			);
+			let outcomes = <Outcomes<T>>::get(market_id);
+			let outcome =
+				outcomes.get(outcome_index as usize).ok_or(Error::<T>::InvalidOutcomeIndex)?;
+			ensure!(
+				outcome.owner == who && !outcome.price.is_zero(),
+				Error::<T>::CallerNotOutcomeOwner
+			);
+
+			Self::release_standing_order(market_id, outcome_index, outcome);
+			if let Some(max_price) = max_price {
+				ensure!(outcome.price < max_price, Error::<T>::PriceTooLow);
+				T::Currency::reserve_named(
+					&HoldReason::StandingOrder.identifier(),
+					&who,
+					max_price.saturating_sub(outcome.price),
+				)?;
+				<StandingOrders<T>>::insert(market_id, outcome_index, (&who, max_price));
+			}
+
+			Self::deposit_event(Event::StandingOrderSet {
+				market_id,
+				outcome_index,
+				who,
+				max_price,
+			});
+
+			Ok(())
+		}
+
+		/// Let the account perform the creator actions of the market. Only the creator can change
+		/// the managers.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::add_market_manager())]
+		pub fn add_market_manager(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			manager: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.creator == who, Error::<T>::CallerNotCreator);
+			ensure!(!Self::is_manager(market_id, &market, &manager), Error::<T>::AlreadyManager);
+			<MarketManagers<T>>::try_append(market_id, &manager)
+				.map_err(|_| Error::<T>::TooManyManagers)?;
+
+			Self::deposit_event(Event::MarketManagerAdded { market_id, manager });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::remove_market_manager())]
+		pub fn remove_market_manager(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			manager: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.creator == who, Error::<T>::CallerNotCreator);
+			<MarketManagers<T>>::try_mutate_exists(market_id, |managers| -> DispatchResult {
+				let list = managers.as_mut().ok_or(Error::<T>::NotManager)?;
+				let index =
+					list.iter().position(|m| *m == manager).ok_or(Error::<T>::NotManager)?;
+				list.remove(index);
+				if list.is_empty() {
+					*managers = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MarketManagerRemoved { market_id, manager });
+
+			Ok(())
+		}
+
+		/// Remove up to `limit` storage items of a market in the `Destroying` status.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::continue_destroy(*limit))]
+		pub fn continue_destroy(
+			origin: OriginFor<T>,
+			#[pallet::compact] market_id: MarketId,
+			#[pallet::compact] limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			ensure!(
+				<MarketsToDestroy<T>>::contains_key(market_id),
+				Error::<T>::InvalidMarketStatus
+			);
+			let removed = Self::destroy_step(market_id, limit);
+
+			Ok(Some(T::WeightInfo::continue_destroy(removed)).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Announces the markets, which close at the block entering the announcement horizon.
+		pub fn on_finalize_impl(n: T::BlockNumber) {
+			let close_block = n.saturating_add(T::CloseAnnouncementHorizon::get());
+			if <MarketCountPerCloseBlock<T>>::get(close_block).is_zero() {
+				return;
+			}
+			// The counter bounds the number of market ids of the block by `CacheSize`.
+			let market_ids = BoundedVec::truncate_from(
+				<MarketIdsPerCloseBlock<T>>::iter_key_prefix(close_block).collect::<Vec<_>>(),
+			);
+			<AnnouncedCloses<T>>::insert(close_block, &market_ids);
+			if T::InformationalEvents::get() {
+				Self::deposit_event(Event::MarketsToClose { close_block, market_ids });
+			}
+		}
+
+		fn schedule_close(end: T::BlockNumber, market_id: MarketId) -> DispatchResult {
+			<MarketCountPerCloseBlock<T>>::try_mutate(end, |count| -> DispatchResult {
+				ensure!(*count < CacheSize::get(), Error::<T>::StorageOverflow(2u8));
+				*count = count.saturating_add(1);
+				Ok(())
+			})?;
+			<MarketIdsPerCloseBlock<T>>::insert(end, market_id, ());
+			// The close block was already announced, if it's within the horizon.
+			let now = <frame_system::Pallet<T>>::block_number();
+			if end < now.saturating_add(T::CloseAnnouncementHorizon::get()) {
+				<AnnouncedCloses<T>>::mutate(end, |market_ids| {
+					let res = market_ids.try_push(market_id);
+					debug_assert!(res.is_ok(), "the close block has already `CacheSize` markets");
+				});
+			}
+			Ok(())
+		}
+
+		fn unschedule_close(end: T::BlockNumber, market_id: MarketId) {
+			if <MarketIdsPerCloseBlock<T>>::take(end, market_id).is_none() {
+				return;
+			}
+			<AnnouncedCloses<T>>::mutate_exists(end, |market_ids| {
+				*market_ids = market_ids
+					.take()
+					.map(|mut ids| {
+						ids.retain(|id| *id != market_id);
+						ids
+					})
+					.filter(|ids| !ids.is_empty());
+			});
+			<MarketCountPerCloseBlock<T>>::mutate_exists(end, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+			});
+		}
+
+		/// Removes a closed market from the schedule of `resolve_expired_reports`. The market is
+		/// scheduled after its reporting deadline or, if it was closed later, after its close. The
+		/// latter is only pending, if the market was closed in this block.
+		fn unschedule_report_deadline(end: T::BlockNumber, market_id: MarketId) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let expiries = [
+				Self::reporting_deadline(end).saturating_add(One::one()),
+				now.saturating_add(One::one()),
+			];
+			for expiry in expiries {
+				if <MarketIdsPerReportDeadline<T>>::take(expiry, market_id).is_some() {
+					<MarketCountPerReportDeadline<T>>::mutate_exists(expiry, |count| {
+						*count =
+							count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+					});
+					return;
+				}
+			}
+		}
+
+		fn close(market_id: MarketId, mut market: MarketOf<T>) {
+			// Overdue markets closed by `close_market` can have a deadline in the past.
+			let now = <frame_system::Pallet<T>>::block_number();
+			let expiry = Self::reporting_deadline(market.end)
+				.saturating_add(One::one())
+				.max(now.saturating_add(One::one()));
+			let scheduled = <MarketCountPerReportDeadline<T>>::mutate(expiry, |count| {
+				if *count >= CacheSize::get() {
+					return false;
+				}
+				*count = count.saturating_add(1);
+				true
+			});
+			// Otherwise the market can still be resolved with `resolve_expired_market`.
+			if scheduled {
+				<MarketIdsPerReportDeadline<T>>::insert(expiry, market_id, ());
+			}
+
+			let outcomes = <Outcomes<T>>::get(market_id);
+			Self::release_standing_orders(market_id, &outcomes);
+			<ClosedSnapshots<T>>::insert(market_id, outcomes);
+			market.status = MarketStatus::Closed;
+			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.closed);
+			Self::deposit_event(Event::MarketClosed { market_id });
+		}
+
+		/// Lets the standing order of the outcome outbid a bid of `price` by `BidIncrement` and
+		/// returns the price of the rebid. The order is released, if the bid reaches its maximum
+		/// price or comes from the owner.
+		fn rebid_standing_order(
+			market_id: MarketId,
+			outcomes: &mut OutcomesOf<T>,
+			outcome_index: u8,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) -> Result<Option<BalanceOf<T>>, DispatchError> {
+			let (owner, max_price) = match <StandingOrders<T>>::get(market_id, outcome_index) {
+				Some(order) => order,
+				None => return Ok(None),
+			};
+			let outcome = outcomes
+				.get_mut(outcome_index as usize)
+				.ok_or(Error::<T>::InvalidOutcomeIndex)?;
+			if price >= max_price || owner == *buyer {
+				Self::release_standing_order(market_id, outcome_index, outcome);
+				return Ok(None);
+			}
+
+			let rebid = Self::standing_rebid(price, max_price);
+			let increase = rebid.saturating_sub(outcome.price);
+			let missing = T::Currency::unreserve_named(
+				&HoldReason::StandingOrder.identifier(),
+				&owner,
+				increase,
+			);
+			debug_assert!(missing.is_zero());
+			T::Currency::reserve_named(&HoldReason::OutcomeBid.identifier(), &owner, increase)?;
+			outcome.price = rebid;
+			Self::log_bid(
+				market_id,
+				BidRecord {
+					buyer: owner.clone(),
+					outcome_index,
+					price: rebid,
+					block: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::StandingOrderRebid {
+				market_id,
+				outcome_index,
+				owner,
+				price: rebid,
+			});
+			Ok(Some(rebid))
+		}
+
+		/// The price, at which a standing order of `max_price` outbids a bid of `price`.
+		fn standing_rebid(price: BalanceOf<T>, max_price: BalanceOf<T>) -> BalanceOf<T> {
+			price.saturating_add(T::BidIncrement::get()).min(max_price)
+		}
+
+		fn release_standing_order(
+			market_id: MarketId,
+			outcome_index: u8,
+			outcome: &Outcome<T::AccountId, BalanceOf<T>>,
+		) {
+			if let Some((owner, max_price)) = <StandingOrders<T>>::take(market_id, outcome_index) {
+				let missing = T::Currency::unreserve_named(
+					&HoldReason::StandingOrder.identifier(),
+					&owner,
+					max_price.saturating_sub(outcome.price),
+				);
+				debug_assert!(missing.is_zero());
+			}
+		}
+
+		fn release_standing_orders(market_id: MarketId, outcomes: &OutcomesOf<T>) {
+			for (outcome_index, outcome) in outcomes.iter().enumerate() {
+				Self::release_standing_order(market_id, outcome_index as u8, outcome);
+			}
+		}
+
+		/// The creator or one of the managers of the market.
+		fn is_manager(market_id: MarketId, market: &MarketOf<T>, who: &T::AccountId) -> bool {
+			market.creator == *who || <MarketManagers<T>>::get(market_id).contains(who)
+		}
+
+		fn ensure_manager(
+			market_id: MarketId,
+			market: &MarketOf<T>,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			ensure!(Self::is_manager(market_id, market, who), Error::<T>::CallerNotCreator);
+			Ok(())
+		}
+
+		fn is_retired(market_id: MarketId, outcome_index: u8) -> bool {
+			<RetiredOutcomes<T>>::get(market_id).contains(&outcome_index)
+		}
+
+		fn is_traded(market_id: MarketId, outcome_index: u8) -> bool {
+			<TradedOutcomes<T>>::get(market_id).contains(&outcome_index)
+		}
+
+		fn note_traded(market_id: MarketId, outcome_index: u8) -> DispatchResult {
+			<TradedOutcomes<T>>::try_mutate(market_id, |traded| {
+				if !traded.contains(&outcome_index) {
+					traded.try_push(outcome_index).map_err(|_| Error::<T>::InvalidOutcomeIndex)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// The outcomes at the close of the market or the current outcomes of an active market.
+		fn closed_outcomes(market_id: MarketId) -> OutcomesOf<T> {
+			<ClosedSnapshots<T>>::get(market_id).unwrap_or_else(|| <Outcomes<T>>::get(market_id))
+		}
+
+		/// Resolves the markets as invalid, whose reporting window expired in the previous block,
+		/// and returns the number of resolved markets.
+		fn resolve_expired_reports(n: T::BlockNumber) -> u32 {
+			let market_ids = <MarketIdsPerReportDeadline<T>>::drain_prefix(n)
+				.map(|(market_id, ())| market_id)
+				.collect::<Vec<_>>();
+			<MarketCountPerReportDeadline<T>>::remove(n);
+
+			let mut resolved = 0u32;
+			for market_id in market_ids {
+				// Reported or resolved markets are left in the schedule and skipped here.
+				let market = match <Markets<T>>::get(market_id) {
+					Some(market) if market.status == MarketStatus::Closed => market,
+					_ => continue,
+				};
+				let res = with_storage_layer(|| Self::resolve_expired(market_id, market));
+				debug_assert!(res.is_ok());
+				resolved = resolved.saturating_add(1);
+			}
+			resolved
+		}
+
+		fn note_transition(
+			market_id: MarketId,
+			select: fn(&mut MarketTransitions) -> &mut BoundedVec<MarketId, MaxTransitionsPerBlock>,
+		) {
+			<MarketTransitionsInBlock<T>>::mutate(|transitions| {
+				if select(transitions).try_push(market_id).is_err() {
+					transitions.overflowed = true;
+				}
+			});
+		}
+
+		fn deposit_transitions_digest() {
+			let transitions = <MarketTransitionsInBlock<T>>::get();
+			if transitions.is_empty() {
+				return;
+			}
+			let digest = (MARKET_TRANSITIONS_DIGEST_PREFIX, transitions).encode();
+			<frame_system::Pallet<T>>::deposit_log(frame_support::sp_runtime::DigestItem::Other(
+				digest,
+			));
+		}
+
+		/// A market stays paused until it is unpaused or until the maximum pause duration is used
+		/// up.
+		pub fn is_paused(market_id: MarketId) -> bool {
+			let pause = <MarketPauses<T>>::get(market_id);
+			match pause.paused_since {
+				Some(paused_since) => {
+					let now = <frame_system::Pallet<T>>::block_number();
+					pause.total_paused.saturating_add(now.saturating_sub(paused_since))
+						< T::MaxPauseDuration::get()
+				},
+				None => false,
+			}
+		}
+
+		/// Pays the fees of the fee schedule from the market pot and returns the rest.
+		fn pay_fees(
+			market_id: MarketId,
+			market: &MarketOf<T>,
+			pot: BalanceOf<T>,
+			flows: &mut BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let market_account = Self::market_account(market_id);
+			let mut rest = pot;
+			for (payee, fee) in Self::fee_payees(market_id, market, pot) {
+				T::Currency::transfer(
+					&market_account,
+					&payee,
+					fee,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				rest = rest.saturating_sub(fee);
+				let (_, paid_out) = flows.entry(payee).or_default();
+				paid_out.saturating_accrue(fee);
+			}
+			Ok(rest)
+		}
+
+		/// The non-zero fees of the fee schedule for the market pot and their payees.
+		fn fee_payees(
+			market_id: MarketId,
+			market: &MarketOf<T>,
+			pot: BalanceOf<T>,
+		) -> Vec<(T::AccountId, BalanceOf<T>)> {
+			let creator_fee = T::FeeSchedule::creator_fee(market_id, pot).min(pot);
+			let mut payees = alloc::vec![(market.creator.clone(), creator_fee)];
+			if let Some((referrer, cut)) = T::FeeSchedule::referral_cut(market_id, creator_fee) {
+				let cut = cut.min(creator_fee);
+				payees[0].1 = creator_fee.saturating_sub(cut);
+				payees.push((referrer, cut));
+			}
+			if let Some((beneficiary, fee)) = T::FeeSchedule::protocol_fee(market_id, pot) {
+				payees.push((beneficiary, fee.min(pot.saturating_sub(creator_fee))));
+			}
+			payees.retain(|(_, fee)| !fee.is_zero());
+			payees
+		}
+
+		/// Moves the position of the outbid owner to the buyer of an outcome in the account
+		/// index. The creator owns the outcomes without bids, which aren't positions.
+		fn index_position(
+			market_id: MarketId,
+			outcomes: &OutcomesOf<T>,
+			previous_owner: &T::AccountId,
+			buyer: &T::AccountId,
+		) {
+			<AccountPositions<T>>::insert(buyer, market_id, ());
+			let still_owns = outcomes
+				.iter()
+				.any(|outcome| outcome.owner == *previous_owner && !outcome.price.is_zero());
+			if !still_owns {
+				<AccountPositions<T>>::remove(previous_owner, market_id);
+			}
+		}
+
+		fn unindex_positions(market_id: MarketId, outcomes: &OutcomesOf<T>) {
+			for outcome in outcomes.iter() {
+				<AccountPositions<T>>::remove(&outcome.owner, market_id);
+			}
+		}
+
+		/// The outcome positions of the account in all unsettled markets.
+		pub fn account_positions(account: T::AccountId) -> Vec<PositionInfo<BalanceOf<T>>> {
+			let mut positions = Vec::new();
+			for market_id in <AccountPositions<T>>::iter_key_prefix(&account) {
+				let market = match <Markets<T>>::get(market_id) {
+					Some(market) => market,
+					None => continue,
+				};
+				let outcomes = Self::closed_outcomes(market_id);
+				let payouts = Self::expected_payouts(market_id, &market, &outcomes);
+				for (outcome_index, outcome) in outcomes.iter().enumerate() {
+					if outcome.owner != account || outcome.price.is_zero() {
+						continue;
+					}
+					let outcome_index = outcome_index as u8;
+					let claimable = payouts
+						.iter()
+						.find(|(index, _)| *index == outcome_index)
+						.map_or_else(Zero::zero, |(_, amount)| *amount);
+					positions.push(PositionInfo {
+						market_id,
+						outcome_index,
+						status: market.status.clone(),
+						escrowed: outcome.price,
+						claimable,
+					});
+				}
+			}
+			positions
+		}
+
+		/// The time weighted average of the implied probability of the outcome over the last
+		/// `window` blocks. The price history is rebuilt from the bid log, so prices before the
+		/// oldest logged bid are taken as zero.
+		pub fn twap(
+			market_id: MarketId,
+			outcome_index: u8,
+			window: T::BlockNumber,
+		) -> Option<Perbill> {
+			use frame_support::sp_runtime::SaturatedConversion;
+			if window.is_zero() {
+				return None;
+			}
+			let mut prices = <Outcomes<T>>::get(market_id)
+				.iter()
+				.map(|outcome| outcome.price.saturated_into::<u128>())
+				.collect::<Vec<_>>();
+			prices.get(outcome_index as usize)?;
+
+			let probability = |prices: &[u128]| -> u128 {
+				let total = prices.iter().fold(0u128, |total, price| total.saturating_add(*price));
+				let part = if total.is_zero() {
+					Perbill::from_rational(1, prices.len() as u128)
+				} else {
+					Perbill::from_rational(
+						prices.get(outcome_index as usize).copied().unwrap_or_default(),
+						total,
+					)
+				};
+				part.deconstruct().into()
+			};
+
+			// Walk back from the current prices and undo one logged bid per step.
+			let now = <frame_system::Pallet<T>>::block_number();
+			let start = now.saturating_sub(window);
+			let log = <BidLogs<T>>::get(market_id);
+			let mut segment_end = now;
+			let mut weighted = 0u128;
+			for (position, record) in log.iter().enumerate().rev() {
+				if record.block <= start {
+					break;
+				}
+				let length = segment_end.saturating_sub(record.block).saturated_into::<u128>();
+				weighted = weighted.saturating_add(probability(&prices).saturating_mul(length));
+				segment_end = record.block;
+				let previous_price = log
+					.iter()
+					.take(position)
+					.rev()
+					.find(|older| older.outcome_index == record.outcome_index)
+					.map_or(0, |older| older.price.saturated_into::<u128>());
+				if let Some(price) = prices.get_mut(record.outcome_index as usize) {
+					*price = previous_price;
+				}
+			}
+			let length = segment_end.saturating_sub(start).saturated_into::<u128>();
+			weighted = weighted.saturating_add(probability(&prices).saturating_mul(length));
+
+			let average = weighted / window.saturated_into::<u128>();
+			Some(Perbill::from_parts(average.saturated_into::<u32>()))
+		}
+
+		fn ensure_valid_bid(
+			market_id: MarketId,
+			outcome_index: u8,
+			price: BalanceOf<T>,
+		) -> Result<(MarketOf<T>, OutcomesOf<T>), BidError> {
+			let market = <Markets<T>>::get(market_id).ok_or(BidError::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Active, BidError::MarketNotActive);
+			ensure!(!Self::is_paused(market_id), BidError::MarketPaused);
+			ensure!(!<RequiredBonds<T>>::contains_key(market_id), BidError::BondTopUpRequired);
+
+			let outcomes = <Outcomes<T>>::get(market_id);
+			let outcome =
+				outcomes.get(outcome_index as usize).ok_or(BidError::InvalidOutcomeIndex)?;
+			ensure!(!Self::is_retired(market_id, outcome_index), BidError::OutcomeRetired);
+			ensure!(
+				outcome.price.is_zero() || <MarketMechanisms<T>>::get(market_id).allows_outbid(),
+				BidError::OutcomeAlreadyOwned
+			);
+			ensure!(outcome.price < price, BidError::PriceTooLow);
+			Ok((market, outcomes))
+		}
+
+		/// Performs the market checks of `buy_outcome` and returns the effect of the bid without
+		/// changing the state. The balance of the buyer isn't checked.
+		pub fn quote_buy(
+			market_id: MarketId,
+			outcome_index: u8,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) -> Result<BuyQuote<T::AccountId, BalanceOf<T>>, BidError> {
+			let (market, mut outcomes) = Self::ensure_valid_bid(market_id, outcome_index, price)?;
+			let outcome =
+				outcomes.get_mut(outcome_index as usize).ok_or(BidError::InvalidOutcomeIndex)?;
+			let rebid = match <StandingOrders<T>>::get(market_id, outcome_index) {
+				Some((owner, max_price)) if price < max_price && owner != *buyer => {
+					Some(Self::standing_rebid(price, max_price))
+				},
+				_ => None,
+			};
+			let refund = match rebid {
+				Some(rebid) => {
+					outcome.price = rebid;
+					None
+				},
+				None => {
+					let refund =
+						(!outcome.price.is_zero()).then(|| (outcome.owner.clone(), outcome.price));
+					outcome.price = price;
+					refund
+				},
+			};
+
+			let favorite = outcomes
+				.iter()
+				.enumerate()
+				.max_by_key(|(_, outcome)| outcome.price)
+				.map_or(outcome_index, |(index, _)| index as u8);
+			let pot = Self::market_pot(market_id, &outcomes);
+			let fees = Self::fee_payees(market_id, &market, pot)
+				.into_iter()
+				.fold(BalanceOf::<T>::zero(), |fees, (_, fee)| fees.saturating_add(fee));
+
+			Ok(BuyQuote { refund, rebid, favorite, pot, fees, queued: T::BatchBidClearing::get() })
+		}
+
+		/// The held outcome bids and the sponsorships of the market.
+		fn market_pot(market_id: MarketId, outcomes: &OutcomesOf<T>) -> BalanceOf<T> {
+			outcomes.iter().fold(
+				T::Currency::free_balance(&Self::market_account(market_id)),
+				|pot, outcome| pot.saturating_add(outcome.price),
+			)
+		}
+
+		/// The payouts of the ranked outcomes of a reported market, which `redeem` would make.
+		fn expected_payouts(
+			market_id: MarketId,
+			market: &MarketOf<T>,
+			outcomes: &OutcomesOf<T>,
+		) -> Vec<(u8, BalanceOf<T>)> {
+			let winner_index = match (&market.status, market.oracle_outcome_report) {
+				(MarketStatus::Reported, Some(winner_index)) => winner_index,
+				_ => return Vec::new(),
+			};
+			let pot = Self::market_pot(market_id, outcomes);
+			let reward = Self::fee_payees(market_id, market, pot)
+				.into_iter()
+				.fold(pot, |reward, (_, fee)| reward.saturating_sub(fee));
+			let mut winner_reward = reward;
+			let mut payouts = Vec::new();
+			let ranking = <RankedReports<T>>::get(market_id);
+			let payout_ratios = <PayoutRatios<T>>::get(market_id);
+			for (outcome_index, ratio) in ranking.iter().zip(payout_ratios.iter()).skip(1) {
+				let amount = ratio.mul_floor(reward);
+				winner_reward = winner_reward.saturating_sub(amount);
+				payouts.push((*outcome_index, amount));
+			}
+			payouts.push((winner_index, winner_reward));
+			payouts
+		}
+
+		fn remove_market(market_id: MarketId) {
+			<Markets<T>>::remove(market_id);
+			Self::unindex_positions(market_id, &<Outcomes<T>>::take(market_id));
+			<PayoutRatios<T>>::remove(market_id);
+			<RankedReports<T>>::remove(market_id);
+			<MarketPauses<T>>::remove(market_id);
+			<OracleAcceptanceDeadlines<T>>::remove(market_id);
+			<MarketSponsors<T>>::remove(market_id);
+			<BidLogs<T>>::remove(market_id);
+			<ScalarOracles<T>>::remove(market_id);
+			<ScalarRanges<T>>::remove(market_id);
+			<DataUpdates<T>>::remove(market_id);
+			<RequiredBonds<T>>::remove(market_id);
+			<ClosedSnapshots<T>>::remove(market_id);
+			<RetiredOutcomes<T>>::remove(market_id);
+			<TradedOutcomes<T>>::remove(market_id);
+			<MarketMechanisms<T>>::remove(market_id);
+			<MarketManagers<T>>::remove(market_id);
+			Self::unindex_locale(market_id);
+			debug_assert!(!<ScalarReports<T>>::contains_key(market_id));
+			debug_assert!(!<OracleBonds<T>>::contains_key(market_id));
+		}
+
+		/// Removes up to `limit` storage items of a destroyed market and returns their number. The
+		/// outcomes are removed one by one, the remaining storage of the market counts as one item.
+		fn destroy_step(market_id: MarketId, limit: u32) -> u32 {
+			let mut removed = 0u32;
+			while removed < limit {
+				removed = removed.saturating_add(1);
+				let outcome = <Outcomes<T>>::mutate(market_id, |outcomes| outcomes.pop());
+				if let Some(outcome) = outcome {
+					<AccountPositions<T>>::remove(&outcome.owner, market_id);
+					continue;
+				}
+				Self::remove_market(market_id);
+				<MarketsToDestroy<T>>::remove(market_id);
+				Self::deposit_event(Event::MarketDestroyed { market_id });
+				break;
+			}
+			removed
+		}
+
+		/// Removes the storage of destroyed markets with the given weight and returns the
+		/// consumed weight.
+		fn destroy_markets(remaining_weight: Weight) -> Weight {
+			let item_weight = T::WeightInfo::continue_destroy(1);
+			let mut budget = match remaining_weight.checked_div(item_weight.ref_time()) {
+				Some(budget) => budget.ref_time().min(u32::MAX as u64) as u32,
+				None => return Weight::zero(),
+			};
+			let mut consumed_weight = Weight::zero();
+			while !budget.is_zero() {
+				let market_id = match <MarketsToDestroy<T>>::iter_keys().next() {
+					Some(market_id) => market_id,
+					None => break,
+				};
+				let removed = Self::destroy_step(market_id, budget);
+				budget = budget.saturating_sub(removed);
+				consumed_weight.saturating_accrue(T::WeightInfo::continue_destroy(removed));
+			}
+			consumed_weight
+		}
+
+		fn log_bid(
+			market_id: MarketId,
+			record: BidRecord<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		) {
+			if T::BidLogLength::get().is_zero() {
+				return;
+			}
+			<BidLogs<T>>::mutate(market_id, |log| {
+				if log.is_full() {
+					log.remove(0);
+				}
+				let res = log.try_push(record);
+				debug_assert!(res.is_ok());
+			});
+		}
+
+		fn deposit_removal_receipt(
+			market_id: MarketId,
+			market: MarketOf<T>,
+			bond: BondDisposition<T::AccountId>,
+		) {
+			let outcomes = Self::closed_outcomes(market_id);
+			let total_volume = outcomes
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |total, outcome| total.saturating_add(outcome.price));
+			let winner = match market.status {
+				MarketStatus::Reported | MarketStatus::Redeemed => market
+					.oracle_outcome_report
+					.and_then(|index| outcomes.get(index as usize))
+					.map(|outcome| outcome.owner.clone()),
+				_ => None,
+			};
+			Self::deposit_event(Event::MarketRemoved {
+				market_id,
+				status: market.status,
+				winner,
+				total_volume,
+				bond,
+			});
+		}
+
+		/// Returns the market pot of an unresolved market to its sponsors.
+		fn refund_sponsors(market_id: MarketId) {
+			let market_account = Self::market_account(market_id);
+			for (sponsor, amount) in <MarketSponsors<T>>::take(market_id) {
+				let res = T::Currency::transfer(
+					&market_account,
+					&sponsor,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				);
+				debug_assert!(res.is_ok());
+				Self::deposit_event(Event::SponsorRefunded { market_id, sponsor, amount });
+			}
+		}
+
+		/// The worst case weight of the `OnParticipation` notifications of a settlement, one per
+		/// entry of the settlement report.
+		fn settlement_participation_weight() -> Weight {
+			T::OnParticipation::participation_weight()
+				.saturating_mul(MaxSettlementEntries::<T>::get().into())
+		}
+
+		fn store_settlement_report(
+			market_id: MarketId,
+			flows: BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+		) -> DispatchResult {
+			let report = flows
+				.into_iter()
+				.map(|(account, (paid_in, paid_out))| {
+					T::OnParticipation::participation_recorded(
+						&account,
+						market_id,
+						Participation::Settlement,
+						paid_out,
+					);
+					SettlementEntry { account, paid_in, paid_out }
+				})
+				.collect::<Vec<_>>();
+			let report = SettlementReportOf::<T>::try_from(report)
+				.map_err(|_| Error::<T>::StorageOverflow(4u8))?;
+			<SettlementReports<T>>::insert(market_id, report);
+			Ok(())
+		}
+
+		fn store_attestation(
+			market_id: MarketId,
+			attester: T::AccountId,
+			attestation: AttestationOf<T>,
+		) -> DispatchResult {
+			ensure!(
+				!<ResolutionAttestations<T>>::contains_key(market_id),
+				Error::<T>::AttestationAlreadyAttached
+			);
+			<ResolutionAttestations<T>>::insert(market_id, (&attester, attestation));
+			Self::deposit_event(Event::ResolutionAttested { market_id, attester });
+			Ok(())
+		}
+
+		/// The settlement report of a resolved market.
+		pub fn settlement_report(
+			market_id: MarketId,
+		) -> Option<Vec<SettlementEntry<T::AccountId, BalanceOf<T>>>> {
+			<SettlementReports<T>>::get(market_id).map(|report| report.into_inner())
+		}
+
+		fn release_oracle_bond(market_id: MarketId, oracle: &T::AccountId) {
+			if let Some(oracle_bond) = <OracleBonds<T>>::take(market_id) {
+				T::Currency::unreserve(oracle, oracle_bond);
+			}
+		}
+
+		/// The close schedule of the inclusive block range `from..=to`, ordered by the close
+		/// block.
+		pub fn markets_closing_in(
+			from: T::BlockNumber,
+			to: T::BlockNumber,
+		) -> Vec<(T::BlockNumber, Vec<MarketId>)> {
+			let mut schedule = <MarketCountPerCloseBlock<T>>::iter_keys()
+				.filter(|block| from <= *block && *block <= to)
+				.map(|block| {
+					(block, <MarketIdsPerCloseBlock<T>>::iter_key_prefix(block).collect::<Vec<_>>())
+				})
+				.collect::<Vec<_>>();
+			schedule.sort_by_key(|(block, _)| *block);
+			schedule
+		}
+
+		/// The markets of the region, optionally filtered by the language.
+		pub fn markets_in_region(region: [u8; 2], language: Option<[u8; 2]>) -> Vec<MarketId> {
+			<MarketsByRegion<T>>::iter_key_prefix(region)
+				.filter(|market_id| match language {
+					Some(language) => matches!(
+						<MarketLocales<T>>::get(market_id),
+						Some(locale) if locale.language == language
+					),
+					None => true,
+				})
+				.collect()
+		}
+
+		fn unindex_locale(market_id: MarketId) {
+			if let Some(locale) = <MarketLocales<T>>::take(market_id) {
+				<MarketsByRegion<T>>::remove(locale.region, market_id);
+			}
+		}
+
+		/// The last block in which the oracle report is accepted.
+		pub fn reporting_deadline(end: T::BlockNumber) -> T::BlockNumber {
+			end.saturating_add(T::ReportingPeriod::get())
+				.saturating_add(T::OracleGracePeriod::get())
+		}
+
+		fn ensure_reporting_window_open(market: &MarketOf<T>) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now <= Self::reporting_deadline(market.end),
+				Error::<T>::ReportingWindowExpired
+			);
+			Ok(())
+		}
+
+		/// Resolves a closed market, whose reporting window expired. Scalar markets resolve to
+		/// the median of the reports, if there are any.
+		fn resolve_expired(market_id: MarketId, market: MarketOf<T>) -> DispatchResult {
+			if <ScalarReports<T>>::decode_len(market_id).unwrap_or(0) > 0 {
+				return Self::resolve_scalar(market_id, market);
+			}
+			Self::resolve_invalid(market_id, market)
+		}
+
+		fn resolve_scalar(market_id: MarketId, mut market: MarketOf<T>) -> DispatchResult {
+			let (lower, upper) =
+				<ScalarRanges<T>>::get(market_id).ok_or(Error::<T>::NotScalarMarket)?;
+			let reports = <ScalarReports<T>>::take(market_id);
+			let mut values = reports.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+			values.sort_unstable();
+			// The lower median for an even number of reports.
+			let median = values
+				.get(values.len().saturating_sub(1) / 2)
+				.copied()
+				.ok_or(Error::<T>::NotScalarMarket)?;
+
+			let tolerance = T::ScalarOutlierTolerance::get().mul_floor(upper.saturating_sub(lower));
+			let oracle_bond = T::OracleBond::get();
+			for (oracle, value) in reports {
+				if value.abs_diff(median) > tolerance {
+					let (_imbalance, missing) = T::Currency::slash_reserved(&oracle, oracle_bond);
+					let amount = oracle_bond.saturating_sub(missing);
+					Self::deposit_event(Event::ScalarReporterSlashed { market_id, oracle, amount });
+				} else {
+					T::Currency::unreserve(&oracle, oracle_bond);
+				}
+			}
+
+			let outcome_amount = <Outcomes<T>>::decode_len(market_id).unwrap_or(0) as u128;
+			let width = upper.saturating_sub(lower).checked_div(outcome_amount).unwrap_or(1).max(1);
+			let outcome_index = median
+				.saturating_sub(lower)
+				.saturating_div(width)
+				.min(outcome_amount.saturating_sub(1)) as u8;
+			// A retired outcome can't win, so the bids are refunded.
+			if Self::is_retired(market_id, outcome_index) {
+				return Self::resolve_invalid(market_id, market);
+			}
+
+			Self::release_oracle_bond(market_id, &market.oracle);
+			market.oracle_outcome_report = Some(outcome_index);
+			market.status = MarketStatus::Reported;
+			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.reported);
+
+			Self::deposit_event(Event::ScalarMarketResolved { market_id, median, outcome_index });
+
+			Ok(())
+		}
+
+		/// Returns the bonds of all scalar reports without slashing.
+		fn release_scalar_reports(market_id: MarketId) {
+			for (oracle, _) in <ScalarReports<T>>::take(market_id) {
+				T::Currency::unreserve(&oracle, T::OracleBond::get());
+			}
+		}
+
+		/// The fallback resolution, if the oracle didn't report in time. Every outcome owner
+		/// gets the price back.
+		fn resolve_invalid(market_id: MarketId, mut market: MarketOf<T>) -> DispatchResult {
+			Self::refund_sponsors(market_id);
+			Self::release_scalar_reports(market_id);
+			let reserve_id = HoldReason::OutcomeBid.identifier();
+			let mut flows = BTreeMap::<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>::new();
+			let outcomes = Self::closed_outcomes(market_id);
+			for outcome in outcomes.iter() {
+				if outcome.price.is_zero() {
+					continue;
+				}
+				let missing =
+					T::Currency::unreserve_named(&reserve_id, &outcome.owner, outcome.price);
+				debug_assert!(missing.is_zero());
+				let (paid_in, paid_out) = flows.entry(outcome.owner.clone()).or_default();
+				paid_in.saturating_accrue(outcome.price);
+				paid_out.saturating_accrue(outcome.price.saturating_sub(missing));
+			}
+			Self::store_settlement_report(market_id, flows)?;
+			Self::unindex_positions(market_id, &outcomes);
+
+			if let Some(oracle_bond) = <OracleBonds<T>>::take(market_id) {
+				let (_imbalance, missing) =
+					T::Currency::slash_reserved(&market.oracle, oracle_bond);
+				debug_assert!(missing.is_zero());
+			}
+
+			market.status = MarketStatus::Invalid;
+			<Markets<T>>::insert(market_id, market);
+			Self::note_transition(market_id, |transitions| &mut transitions.invalid);
+
+			Self::deposit_event(Event::MarketResolvedInvalid { market_id });
+
+			Ok(())
+		}
+
+		/// The health metrics of all markets. Iterates all markets, so it's meant for off-chain
+		/// callers only.
+		pub fn market_metrics() -> MarketMetrics<BalanceOf<T>> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut metrics = MarketMetrics::<BalanceOf<T>>::default();
+			for (market_id, market) in <Markets<T>>::iter() {
+				match market.status {
+					MarketStatus::Closed if now > Self::reporting_deadline(market.end) => {
+						metrics.stuck_closed.saturating_inc();
+					},
+					MarketStatus::Reported | MarketStatus::Redeemed | MarketStatus::Invalid
+						if now.saturating_sub(market.end)
+							> T::MarketCreatorClearStorageTime::get() =>
+					{
+						metrics.past_redemption_deadline.saturating_inc();
+					},
+					_ => {},
+				}
+				// The funds of destroyed markets are already released.
+				if market.status == MarketStatus::Destroying {
+					continue;
+				}
+
+				let market_account = Self::market_account(market_id);
+				metrics
+					.total_value_locked
+					.saturating_accrue(T::Currency::total_balance(&market_account));
+				// The bids of resolved markets are collected or returned.
+				if !matches!(market.status, MarketStatus::Redeemed | MarketStatus::Invalid) {
+					for outcome in Self::closed_outcomes(market_id).iter() {
+						metrics.total_value_locked.saturating_accrue(outcome.price);
+					}
+				}
+				metrics.bonds_reserved.saturating_accrue(market.bond);
+			}
+			for oracle_bond in <OracleBonds<T>>::iter_values() {
+				metrics.bonds_reserved.saturating_accrue(oracle_bond);
+			}
+			metrics
+		}
+
+		/// The economically relevant parameters of the pallet.
+		pub fn market_constants() -> MarketConstants<BalanceOf<T>, T::BlockNumber> {
+			MarketConstants {
+				creator_bond: T::CreatorBond::get(),
+				oracle_bond: T::OracleBond::get(),
+				keeper_tip: T::FeeSchedule::keeper_tip(),
+				min_market_period: T::MinMarketPeriod::get(),
+				max_pause_duration: T::MaxPauseDuration::get(),
+				oracle_acceptance_period: T::OracleAcceptancePeriod::get(),
+				reporting_period: T::ReportingPeriod::get(),
+				oracle_grace_period: T::OracleGracePeriod::get(),
+				market_creator_clear_storage_time: T::MarketCreatorClearStorageTime::get(),
+				max_outcomes: T::MaxOutcomes::get(),
+				max_bids_per_block: T::MaxBidsPerBlock::get(),
+				max_queued_bids: T::MaxQueuedBids::get(),
+				max_sponsors: T::MaxSponsors::get(),
+				max_markets_per_close_block: CacheSize::get(),
+				bid_log_length: T::BidLogLength::get(),
+				batch_bid_clearing: T::BatchBidClearing::get(),
+			}
+		}
+
+		/// Performs all checks of `create_market` without changing the state and returns the id
+		/// the market would get.
+		pub fn validate_market_params(
+			params: MarketParams<T::AccountId, T::BlockNumber>,
+		) -> Result<MarketId, CreateMarketError> {
+			let outcome_data = OutcomeDataOf::<T>::try_from(params.outcome_data)
+				.map_err(|_| CreateMarketError::TooManyOutcomes)?;
+			let payout_ratios = PayoutRatiosOf::<T>::try_from(params.payout_ratios)
+				.map_err(|_| CreateMarketError::InvalidPayoutRatios)?;
+			Self::ensure_valid_market_params(
+				&params.creator,
+				&outcome_data,
+				params.end,
+				&params.oracle,
+				&payout_ratios,
+				params.mechanism,
+			)
+		}
+
+		fn creation_fee(creator: &T::AccountId) -> BalanceOf<T> {
+			if T::CreationFeeExempt::contains(creator) {
+				Zero::zero()
+			} else {
+				T::CreationFee::get()
+			}
+		}
+
+		fn ensure_valid_market_params(
+			creator: &T::AccountId,
+			outcome_data: &OutcomeDataOf<T>,
+			end: T::BlockNumber,
+			oracle: &T::AccountId,
+			payout_ratios: &PayoutRatiosOf<T>,
+			mechanism: Mechanism,
+		) -> Result<MarketId, CreateMarketError> {
+			ensure!(mechanism.is_supported(), CreateMarketError::MechanismNotSupported);
+			ensure!(
+				<MarketsCreatedInBlock<T>>::get() < T::MaxMarketsPerBlock::get(),
+				CreateMarketError::TooManyMarketsThisBlock
+			);
+
+			let bond = T::CreatorBond::get();
+			// The creator is aware of the oracle role, if the creator is the oracle.
+			let oracle_bond = if oracle == creator { T::OracleBond::get() } else { Zero::zero() };
+			let fee = Self::creation_fee(creator);
+			// TODO 11:: Why do we check `can_reserve` here? Why not just using `reserve` alone?
+			ensure!(
+				T::Currency::can_reserve(
+					creator,
+					bond.saturating_add(oracle_bond).saturating_add(fee)
+				),
+				CreateMarketError::InsufficientCreatorBalance
+			);
+
+			ensure!(!outcome_data.is_empty(), CreateMarketError::OutcomeAmountTooLow);
+			let unique_data = outcome_data.iter().collect::<BTreeSet<_>>();
+			ensure!(
+				unique_data.len() == outcome_data.len(),
+				CreateMarketError::DuplicateOutcomeData
+			);
+			// `MaxOutcomes` fits into `u8`, which is checked in the `integrity_test`.
+			let outcome_amount = outcome_data.len() as u8;
+			Self::ensure_valid_payout_ratios(payout_ratios, outcome_amount)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				end.saturating_sub(now) >= T::MinMarketPeriod::get(),
+				CreateMarketError::BelowMinMarketPeriod
+			);
+
+			let market_id = Self::market_counter();
+			ensure!(market_id.checked_add(1).is_some(), CreateMarketError::MarketCounterOverflow);
+
+			let scheduled = MarketCountPerCloseBlock::<T>::get(end);
+			ensure!(scheduled < CacheSize::get(), CreateMarketError::CloseScheduleFull);
+
+			Ok(market_id)
+		}
+
+		fn ensure_valid_payout_ratios(
+			payout_ratios: &PayoutRatiosOf<T>,
+			outcome_amount: u8,
+		) -> Result<(), CreateMarketError> {
+			if payout_ratios.is_empty() {
+				return Ok(());
+			}
+			ensure!(
+				payout_ratios.len() <= outcome_amount as usize,
+				CreateMarketError::InvalidPayoutRatios
+			);
+			ensure!(
+				payout_ratios.iter().all(|ratio| !ratio.is_zero()),
+				CreateMarketError::InvalidPayoutRatios
+			);
+			let total =
+				payout_ratios.iter().map(|ratio| u32::from(ratio.deconstruct())).sum::<u32>();
+			ensure!(total == 100, CreateMarketError::InvalidPayoutRatios);
+			Ok(())
+		}
+
+		/// Clears the queued bids of the last block and returns the number of cleared bids.
+		pub fn clear_queued_bids() -> u32 {
+			let queued_bids = <QueuedBids<T>>::take();
+			let queued_bids_len = queued_bids.len() as u32;
+			if queued_bids.is_empty() {
+				return queued_bids_len;
+			}
+
+			// The highest bid per outcome wins. For equal prices the earlier bid wins.
+			let mut winners = BTreeMap::<(MarketId, u8), usize>::new();
+			for (index, bid) in queued_bids.iter().enumerate() {
+				winners
+					.entry((bid.market_id, bid.outcome_index))
+					.and_modify(|winner| {
+						if queued_bids[*winner].price < bid.price {
+							*winner = index;
+						}
+					})
+					.or_insert(index);
+			}
+
+			for (index, bid) in queued_bids.into_iter().enumerate() {
+				let is_winner = winners.get(&(bid.market_id, bid.outcome_index)) == Some(&index);
+				if is_winner && Self::settle_queued_bid(&bid).is_ok() {
+					continue;
+				}
+
+				let missing = T::Currency::unreserve_named(
+					&HoldReason::OutcomeBid.identifier(),
+					&bid.buyer,
+					bid.price,
+				);
+				debug_assert!(missing.is_zero());
+				Self::deposit_event(Event::BidRefunded {
+					market_id: bid.market_id,
+					outcome_index: bid.outcome_index,
+					buyer: bid.buyer,
+					price: bid.price,
+				});
+			}
+
+			queued_bids_len
+		}
+
+		fn settle_queued_bid(bid: &QueuedBid<AccountIdOf<T>, BalanceOf<T>>) -> DispatchResult {
+			let market = <Markets<T>>::get(bid.market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(market.status == MarketStatus::Active, Error::<T>::MarketNotActive);
+			ensure!(!Self::is_paused(bid.market_id), Error::<T>::MarketPaused);
+
+			let mut outcomes = <Outcomes<T>>::get(bid.market_id);
+			let outcome = outcomes
+				.get(bid.outcome_index as usize)
+				.ok_or(Error::<T>::InvalidOutcomeIndex)?;
+			ensure!(
+				!Self::is_retired(bid.market_id, bid.outcome_index),
+				Error::<T>::OutcomeRetired
+			);
+			ensure!(outcome.price < bid.price, Error::<T>::PriceTooLow);
+
+			if Self::rebid_standing_order(
+				bid.market_id,
+				&mut outcomes,
+				bid.outcome_index,
+				&bid.buyer,
+				bid.price,
+			)?
+			.is_some()
+			{
+				<Outcomes<T>>::insert(bid.market_id, outcomes);
+				let missing = T::Currency::unreserve_named(
+					&HoldReason::OutcomeBid.identifier(),
+					&bid.buyer,
+					bid.price,
+				);
+				debug_assert!(missing.is_zero());
+				Self::deposit_event(Event::BidRefunded {
+					market_id: bid.market_id,
+					outcome_index: bid.outcome_index,
+					buyer: bid.buyer.clone(),
+					price: bid.price,
+				});
+				return Ok(());
+			}
+
+			// The price of the queued bid is already held on the buyer.
+			let previous_owner = <MarketMechanisms<T>>::get(bid.market_id).trade::<T>(
+				bid.market_id,
+				&mut outcomes,
+				bid.outcome_index,
+				&bid.buyer,
+				bid.price,
+			)?;
+
+			Self::index_position(bid.market_id, &outcomes, &previous_owner, &bid.buyer);
+			<Outcomes<T>>::insert(bid.market_id, outcomes);
+			Self::log_bid(
+				bid.market_id,
+				BidRecord {
+					buyer: bid.buyer.clone(),
+					outcome_index: bid.outcome_index,
+					price: bid.price,
+					block: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			T::OnParticipation::participation_recorded(
+				&bid.buyer,
+				bid.market_id,
+				Participation::Bid,
+				bid.price,
+			);
+
+			Self::deposit_event(Event::OutcomeBought {
+				market_id: bid.market_id,
+				outcome_index: bid.outcome_index,
+				buyer: bid.buyer.clone(),
+			});
+
+			Ok(())
+		}
+
+		// TODO 22: What could be the purpose of this function?
+		pub fn g(o: OutcomesOf<T>, i: u8) -> Result<BalanceOf<T>, DispatchError> {
+			use frame_support::sp_runtime::SaturatedConversion;
+			let t = o
+				.iter()
+				.map(|j| j.price.saturated_into::<u128>())
+				.sum::<u128>()
+				.saturated_into::<BalanceOf<T>>();
+			let u = o.get(i as usize).ok_or(Error::<T>::Invalid)?;
+			Ok(u.p(t))
+		}
+
+		/// Announces the highest outcome of `count` markets. The markets are sampled from twice as
+		/// many candidates at a random position, weighted by the number of their recent bids. The
+		/// seed is the parent block hash, so the sample can be reproduced.
+		pub fn emit_highest_outcomes(count: usize) -> Weight {
+			use frame_support::sp_runtime::traits::Hash;
+			let seed = <frame_system::Pallet<T>>::parent_hash();
+			let random = |nonce: u32| -> u64 {
+				let hash = T::Hashing::hash_of(&(seed, nonce));
+				let mut bytes = [0u8; 8];
+				bytes.copy_from_slice(&hash.as_ref()[..8]);
+				u64::from_le_bytes(bytes)
+			};
+
+			let candidate_limit = count.saturating_mul(2).min(<Outcomes<T>>::count() as usize);
+			let market_counter = Self::market_counter().max(1);
+			let start = MarketId::from(random(0)) % market_counter;
+			let start_key = <Outcomes<T>>::hashed_key_for(start);
+			let mut seen = BTreeSet::new();
+			// Only the ids are kept, the outcomes are decoded for the picked markets.
+			let mut candidates = <Outcomes<T>>::iter_keys_from(start_key)
+				.chain(<Outcomes<T>>::iter_keys())
+				.filter(|market_id| seen.insert(*market_id))
+				.take(candidate_limit)
+				.map(|market_id| {
+					let bids = <BidLogs<T>>::decode_len(market_id).unwrap_or(0) as u64;
+					(market_id, bids.saturating_add(1))
+				})
+				.collect::<Vec<_>>();
+
+			for nonce in 1..=count as u32 {
+				let total_activity = candidates.iter().map(|(_, bids)| *bids).sum::<u64>();
+				if total_activity.is_zero() {
+					break;
+				}
+				let mut pick = random(nonce) % total_activity;
+				let position = candidates
+					.iter()
+					.position(|(_, bids)| {
+						if pick < *bids {
+							return true;
+						}
+						pick = pick.saturating_sub(*bids);
+						false
+					})
+					.unwrap_or(0);
+				let (market_id, _) = candidates.swap_remove(position);
+
+				let highest_outcome = <Outcomes<T>>::get(market_id)
+					.iter()
+					.enumerate()
+					.max_by_key(|(_, outcome)| outcome.price)
+					.map(|(index, _)| index as u8);
+				Self::deposit_event(Event::HighestOutcome { market_id, highest_outcome, seed });
+			}
+			T::WeightInfo::emit_highest_outcomes(count as u32)
+		}
+
+		pub fn market_account(market_id: MarketId) -> AccountIdOf<T> {
+			use frame_support::sp_runtime::traits::{Hash, TrailingZeroInput};
+			// Hash the full market id instead of truncating it, so that market accounts can't
+			// collide for account id types smaller than the encoded sub account.
+			let entropy = T::Hashing::hash_of(&(
+				b"modl",
+				T::PalletId::get(),
+				MARKET_ACCOUNT_VERSION,
+				market_id,
+			));
+			Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+				.expect("infinite length input; no invalid inputs for type; qed")
+		}
+
+		/// The market account derivation before `MARKET_ACCOUNT_VERSION` 1.
+		pub fn legacy_market_account(market_id: MarketId) -> AccountIdOf<T> {
+			use frame_support::sp_runtime::traits::AccountIdConversion;
+			T::PalletId::get().into_sub_account_truncating(market_id)
+		}
+	}
+
+	impl<T: Config> MarketApi for Pallet<T>
+	{
+		type MarketId = MarketId;
+		type AccountId = T::AccountId;
+		type Balance = BalanceOf<T>;
+		type BlockNumber = T::BlockNumber;
+
+		fn get_market(market_id: &Self::MarketId) -> Result<(Weight, MarketOf<T>), DispatchError> {
+			let weight = T::DbWeight::get().reads(1);
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			Ok((weight, market))
+		}
+
+		fn market_exists(market_id: &Self::MarketId) -> bool {
+			<Markets<T>>::contains_key(market_id)
+		}
+
+		fn market_status(market_id: &Self::MarketId) -> Option<MarketStatus> {
+			use frame_support::sp_io::storage;
+			// The status is the last field of `Market` and encoded in a single byte, so only that
+			// byte is read and decoded.
+			let key = <Markets<T>>::hashed_key_for(market_id);
+			let len = storage::read(&key, &mut [], 0)?;
+			let mut status = [0u8; 1];
+			storage::read(&key, &mut status, len.checked_sub(1)?)?;
+			MarketStatus::decode(&mut &status[..]).ok()
+		}
+
+		fn resolve_market(
+			market_id: &Self::MarketId,
+			resolution: Resolution,
+		) -> DispatchResultWithPostInfo {
+			let mut market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(
+				matches!(
+					market.status,
+					MarketStatus::Closed | MarketStatus::Reported | MarketStatus::Disputed
+				),
+				Error::<T>::InvalidMarketStatus
+			);
+			let market_id = *market_id;
+
+			match resolution {
+				Resolution::Outcome(outcome_index) => {
+					let outcome_amount = <Outcomes<T>>::decode_len(market_id).unwrap_or(0);
+					ensure!(
+						(outcome_index as usize) < outcome_amount,
+						Error::<T>::InvalidOutcomeIndex
+					);
+					ensure!(
+						!Self::is_retired(market_id, outcome_index),
+						Error::<T>::OutcomeRetired
+					);
+					// An external resolution has a single winner, a previous ranking is dropped.
+					<RankedReports<T>>::remove(market_id);
+					// The attestation belongs to the replaced resolution.
+					<ResolutionAttestations<T>>::remove(market_id);
+					Self::release_oracle_bond(market_id, &market.oracle);
+					Self::release_scalar_reports(market_id);
+					let reported = market.status == MarketStatus::Reported;
+					market.oracle_outcome_report = Some(outcome_index);
+					market.status = MarketStatus::Reported;
+					<Markets<T>>::insert(market_id, market);
+					if !reported {
+						Self::note_transition(market_id, |transitions| &mut transitions.reported);
+					}
+					Self::deposit_event(Event::MarketReported {
+						market_id,
+						oracle_report_outcome: outcome_index,
+					});
+					Ok(Some(T::WeightInfo::resolve_market_outcome()).into())
+				},
+				Resolution::Invalid => {
+					let outcome_amount = <Outcomes<T>>::decode_len(market_id).unwrap_or(0) as u32;
+					Self::resolve_invalid(market_id, market)?;
+					<ResolutionAttestations<T>>::remove(market_id);
+					Ok(Some(
+						T::WeightInfo::resolve_market_invalid(outcome_amount)
+							.saturating_add(Self::settlement_participation_weight()),
+					)
+					.into())
+				},
+			}
+		}
+
+		fn dispute_market(market_id: &Self::MarketId) -> DispatchResult {
+			<Markets<T>>::try_mutate(market_id, |market| -> DispatchResult {
+				let market = market.as_mut().ok_or(Error::<T>::MarketNotFound)?;
+				ensure!(market.status == MarketStatus::Reported, Error::<T>::InvalidMarketStatus);
+				market.status = MarketStatus::Disputed;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::MarketDisputed { market_id: *market_id });
+			Ok(())
+		}
+
+		fn attest_resolution(
+			market_id: &Self::MarketId,
+			attester: Self::AccountId,
+			attestation: Vec<u8>,
+		) -> DispatchResult {
+			let market = <Markets<T>>::get(market_id).ok_or(Error::<T>::MarketNotFound)?;
+			ensure!(
+				matches!(
+					market.status,
+					MarketStatus::Reported | MarketStatus::Redeemed | MarketStatus::Invalid
+				),
+				Error::<T>::InvalidMarketStatus
+			);
+			let attestation = AttestationOf::<T>::try_from(attestation)
+				.map_err(|_| Error::<T>::AttestationTooLong)?;
+			Self::store_attestation(*market_id, attester, attestation)
+		}
+	}
+}
+
+/// The final resolution of a market pushed by an external resolver like a dispute pallet.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+	/// The outcome with the given index wins the whole market.
+	Outcome(u8),
+	/// All bids are refunded.
+	Invalid,
+}
+
+// TODO 23: Imagine this trait is defined outside of this pallet. Why could this be useful?
+pub trait MarketApi {
+	type MarketId;
+	type AccountId;
+	type Balance;
+	type BlockNumber;
+
+	fn get_market(
+		market_id: &Self::MarketId,
+	) -> Result<
+		(
+			frame_support::pallet_prelude::Weight,
+			Market<Self::AccountId, Self::BlockNumber, Self::Balance>,
+		),
+		frame_support::pallet_prelude::DispatchError,
+	>;
+
+	/// Checks the existence of the market without decoding it.
+	fn market_exists(market_id: &Self::MarketId) -> bool;
+
+	/// The status of the market, which is decoded without the rest of the market.
+	fn market_status(market_id: &Self::MarketId) -> Option<MarketStatus>;
+
+	fn resolve_market(
+		market_id: &Self::MarketId,
+		resolution: Resolution,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo;
+
+	/// Freezes the funds of a reported market until it's resolved with `resolve_market`.
+	fn dispute_market(market_id: &Self::MarketId) -> frame_support::dispatch::DispatchResult;
+
+	/// Attaches the attestation of the resolving party to the final resolution of the market.
+	fn attest_resolution(
+		market_id: &Self::MarketId,
+		attester: Self::AccountId,
+		attestation: alloc::vec::Vec<u8>,
+	) -> frame_support::dispatch::DispatchResult;
+}
+
+/// The fee policy of the markets. The fees are taken from the market pot on redemption before the
+/// winners are paid out.
+pub trait FeeSchedule<AccountId, Balance> {
+	/// The fee of the market creator.
+	fn creator_fee(market_id: MarketId, pot: Balance) -> Balance;
+
+	/// The protocol fee and its beneficiary.
+	fn protocol_fee(market_id: MarketId, pot: Balance) -> Option<(AccountId, Balance)>;
+
+	/// The referrer of the market and its cut of the creator fee.
+	fn referral_cut(market_id: MarketId, creator_fee: Balance) -> Option<(AccountId, Balance)>;
+
+	/// The tip from the creator bond for the keeper, who closes an overdue market.
+	fn keeper_tip() -> Balance;
+}
+
+/// Takes no fees from the market pot and tips keepers with `KeeperTip`.
+pub struct DefaultFeeSchedule<KeeperTip>(core::marker::PhantomData<KeeperTip>);
+
+impl<AccountId, Balance, KeeperTip> FeeSchedule<AccountId, Balance>
+	for DefaultFeeSchedule<KeeperTip>
+where
+	Balance: Zero,
+	KeeperTip: frame_support::traits::Get<Balance>,
+{
+	fn creator_fee(_: MarketId, _: Balance) -> Balance {
+		Zero::zero()
+	}
+
+	fn protocol_fee(_: MarketId, _: Balance) -> Option<(AccountId, Balance)> {
+		None
+	}
+
+	fn referral_cut(_: MarketId, _: Balance) -> Option<(AccountId, Balance)> {
+		None
+	}
+
+	fn keeper_tip() -> Balance {
+		KeeperTip::get()
+	}
+}
+
+/// The kind of activity, which is reported to `OnParticipation`.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Participation {
+	/// The account bought an outcome for the amount.
+	Bid,
+	/// The market was resolved and the amount was paid out to the account.
+	Settlement,
+}
+
+/// A hook for runtime-side engagement programs, which track the activity of accounts without
+/// parsing the pallet events. Several programs can be combined in a tuple.
+pub trait OnParticipation<AccountId, Balance: Copy> {
+	/// Called on every bid and on the settlement of every participant of a resolved market.
+	fn participation_recorded(
+		who: &AccountId,
+		market_id: MarketId,
+		participation: Participation,
+		amount: Balance,
+	);
+
+	/// The worst case weight of one `participation_recorded` call, which is charged by the
+	/// calls and hooks of the pallet.
+	fn participation_weight() -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, Balance: Copy> OnParticipation<AccountId, Balance> for Tuple {
+	fn participation_recorded(
+		who: &AccountId,
+		market_id: MarketId,
+		participation: Participation,
+		amount: Balance,
+	) {
+		for_tuples!( #( Tuple::participation_recorded(who, market_id, participation, amount); )* );
+	}
+
+	fn participation_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight.saturating_accrue(Tuple::participation_weight()); )* );
+		weight
+	}
 }
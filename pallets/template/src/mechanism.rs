@@ -0,0 +1,190 @@
+//! The trading mechanisms of the markets. The market lifecycle from the creation to the
+//! resolution is shared by all mechanisms. A mechanism only defines how the outcomes are traded
+//! and how the stakes of the participants are collected into the market pot on redemption.
+
+use crate::{BalanceOf, Config, Error, HoldReason, MarketId, OutcomesOf, Pallet};
+use alloc::collections::BTreeMap;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	sp_runtime::traits::{Saturating, Zero},
+	traits::{BalanceStatus, NamedReservableCurrency},
+};
+use scale_info::TypeInfo;
+
+/// The trading mechanism of a market, which is selected at the creation of the market.
+#[derive(Decode, Encode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mechanism {
+	/// Each outcome has a single owner. A higher bid replaces the owner, whose bid is refunded.
+	#[default]
+	EnglishAuction,
+	/// The participants stake on the outcomes and the winners share the pot.
+	Parimutuel,
+	/// The outcome shares are traded against an automated market maker.
+	Amm,
+	/// The outcome shares are traded with limit orders.
+	OrderBook,
+	/// The first bid buys the outcome, which can't be outbid afterwards.
+	FixedOwner,
+}
+
+impl Mechanism {
+	/// Markets can only be created with the implemented mechanisms.
+	pub fn is_supported(&self) -> bool {
+		matches!(self, Mechanism::EnglishAuction | Mechanism::FixedOwner)
+	}
+
+	/// Whether a higher bid replaces the owner of a bought outcome.
+	pub fn allows_outbid(&self) -> bool {
+		!matches!(self, Mechanism::FixedOwner)
+	}
+
+	pub(crate) fn trade<T: Config>(
+		self,
+		market_id: MarketId,
+		outcomes: &mut OutcomesOf<T>,
+		outcome_index: u8,
+		buyer: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		match self {
+			Mechanism::EnglishAuction => <EnglishAuction as TradingMechanism<T>>::trade(
+				market_id,
+				outcomes,
+				outcome_index,
+				buyer,
+				price,
+			),
+			Mechanism::FixedOwner => <FixedOwner as TradingMechanism<T>>::trade(
+				market_id,
+				outcomes,
+				outcome_index,
+				buyer,
+				price,
+			),
+			_ => Err(Error::<T>::MechanismNotSupported.into()),
+		}
+	}
+
+	pub(crate) fn collect<T: Config>(
+		self,
+		market_id: MarketId,
+		outcomes: &OutcomesOf<T>,
+		flows: &mut BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		match self {
+			Mechanism::EnglishAuction => {
+				<EnglishAuction as TradingMechanism<T>>::collect(market_id, outcomes, flows)
+			},
+			Mechanism::FixedOwner => {
+				<FixedOwner as TradingMechanism<T>>::collect(market_id, outcomes, flows)
+			},
+			_ => Err(Error::<T>::MechanismNotSupported.into()),
+		}
+	}
+}
+
+/// The mechanism specific part of trading and settlement.
+pub trait TradingMechanism<T: Config> {
+	/// Transfers the outcome to the buyer for the price and returns the previous owner. The
+	/// price is held on the buyer by the caller.
+	fn trade(
+		market_id: MarketId,
+		outcomes: &mut OutcomesOf<T>,
+		outcome_index: u8,
+		buyer: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<T::AccountId, DispatchError>;
+
+	/// Moves the stakes of the participants into the market account and adds them to the paid
+	/// in amounts of `flows`.
+	fn collect(
+		market_id: MarketId,
+		outcomes: &OutcomesOf<T>,
+		flows: &mut BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult;
+}
+
+/// See `Mechanism::EnglishAuction`.
+pub struct EnglishAuction;
+
+impl<T: Config> TradingMechanism<T> for EnglishAuction {
+	fn trade(
+		_market_id: MarketId,
+		outcomes: &mut OutcomesOf<T>,
+		outcome_index: u8,
+		buyer: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		let outcome = outcomes
+			.get_mut(outcome_index as usize)
+			.ok_or(Error::<T>::InvalidOutcomeIndex)?;
+		if !outcome.price.is_zero() {
+			let missing = T::Currency::unreserve_named(
+				&HoldReason::OutcomeBid.identifier(),
+				&outcome.owner,
+				outcome.price,
+			);
+			debug_assert!(missing.is_zero());
+		}
+
+		outcome.price = price;
+		Ok(core::mem::replace(&mut outcome.owner, buyer.clone()))
+	}
+
+	fn collect(
+		market_id: MarketId,
+		outcomes: &OutcomesOf<T>,
+		flows: &mut BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		let market_account = Pallet::<T>::market_account(market_id);
+		let reserve_id = HoldReason::OutcomeBid.identifier();
+		for outcome in outcomes.iter().filter(|outcome| !outcome.price.is_zero()) {
+			let missing = T::Currency::repatriate_reserved_named(
+				&reserve_id,
+				&outcome.owner,
+				&market_account,
+				outcome.price,
+				BalanceStatus::Free,
+			)?;
+			debug_assert!(missing.is_zero());
+			let (paid_in, _) = flows.entry(outcome.owner.clone()).or_default();
+			paid_in.saturating_accrue(outcome.price.saturating_sub(missing));
+		}
+		Ok(())
+	}
+}
+
+/// See `Mechanism::FixedOwner`.
+pub struct FixedOwner;
+
+impl<T: Config> TradingMechanism<T> for FixedOwner {
+	fn trade(
+		market_id: MarketId,
+		outcomes: &mut OutcomesOf<T>,
+		outcome_index: u8,
+		buyer: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		let outcome =
+			outcomes.get(outcome_index as usize).ok_or(Error::<T>::InvalidOutcomeIndex)?;
+		ensure!(outcome.price.is_zero(), Error::<T>::OutcomeAlreadyOwned);
+		// Nothing is refunded, because the outcome has no bid yet.
+		<EnglishAuction as TradingMechanism<T>>::trade(
+			market_id,
+			outcomes,
+			outcome_index,
+			buyer,
+			price,
+		)
+	}
+
+	fn collect(
+		market_id: MarketId,
+		outcomes: &OutcomesOf<T>,
+		flows: &mut BTreeMap<T::AccountId, (BalanceOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		<EnglishAuction as TradingMechanism<T>>::collect(market_id, outcomes, flows)
+	}
+}
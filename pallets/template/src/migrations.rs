@@ -0,0 +1,383 @@
+use crate::{
+	AccountPositions, CacheSize, Config, HoldReason, MarketCountPerCloseBlock, MarketId,
+	MarketStatus, Markets, Outcomes, Pallet, QueuedBids,
+};
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::traits::Zero,
+	traits::{Currency, ExistenceRequirement, NamedReservableCurrency, OnRuntimeUpgrade},
+};
+
+pub mod v1 {
+	use super::*;
+
+	/// Moves the funds of all markets from the truncated sub accounts to the hashed market
+	/// accounts of `MARKET_ACCOUNT_VERSION` 1.
+	pub struct MigrateMarketAccounts<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateMarketAccounts<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut total_weight = T::DbWeight::get().reads(1);
+
+			let on_chain_version = StorageVersion::get::<Pallet<T>>();
+			if on_chain_version != 0 {
+				return total_weight;
+			}
+
+			for market_id in <Markets<T>>::iter_keys() {
+				total_weight = total_weight.saturating_add(T::DbWeight::get().reads_writes(3, 2));
+
+				let legacy_account = Pallet::<T>::legacy_market_account(market_id);
+				let amount = T::Currency::free_balance(&legacy_account);
+				if amount.is_zero() {
+					continue;
+				}
+
+				let res = T::Currency::transfer(
+					&legacy_account,
+					&Pallet::<T>::market_account(market_id),
+					amount,
+					ExistenceRequirement::AllowDeath,
+				);
+				debug_assert!(res.is_ok());
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			total_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(Vec::new())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 1, "storage version not updated");
+			for market_id in <Markets<T>>::iter_keys() {
+				let legacy_account = Pallet::<T>::legacy_market_account(market_id);
+				ensure!(
+					T::Currency::free_balance(&legacy_account).is_zero(),
+					"legacy market account still holds funds"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Moves the prices of the owned outcomes of unsettled markets, including reported markets,
+	/// which aren't redeemed yet, from the market accounts back to the outcome owners, where
+	/// they are held with `HoldReason::OutcomeBid`.
+	pub struct MigrateBidsToHolds<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateBidsToHolds<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut total_weight = T::DbWeight::get().reads(1);
+
+			let on_chain_version = StorageVersion::get::<Pallet<T>>();
+			if on_chain_version != 1 {
+				return total_weight;
+			}
+
+			let reserve_id = HoldReason::OutcomeBid.identifier();
+			for (market_id, market) in <Markets<T>>::iter() {
+				total_weight = total_weight.saturating_add(T::DbWeight::get().reads(2));
+				if matches!(market.status, MarketStatus::Redeemed | MarketStatus::Invalid) {
+					continue;
+				}
+
+				let market_account = Pallet::<T>::market_account(market_id);
+				for outcome in <Outcomes<T>>::get(market_id).iter() {
+					if outcome.price.is_zero() {
+						continue;
+					}
+					total_weight =
+						total_weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+
+					let res = T::Currency::transfer(
+						&market_account,
+						&outcome.owner,
+						outcome.price,
+						ExistenceRequirement::AllowDeath,
+					)
+					.and_then(|_| {
+						T::Currency::reserve_named(&reserve_id, &outcome.owner, outcome.price)
+					});
+					debug_assert!(res.is_ok());
+				}
+			}
+
+			// The queued bids of the last block are cleared after the migration.
+			total_weight = total_weight.saturating_add(T::DbWeight::get().reads(1));
+			for bid in <QueuedBids<T>>::get().iter() {
+				total_weight = total_weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+
+				let res = T::Currency::transfer(
+					&Pallet::<T>::market_account(bid.market_id),
+					&bid.buyer,
+					bid.price,
+					ExistenceRequirement::AllowDeath,
+				)
+				.and_then(|_| T::Currency::reserve_named(&reserve_id, &bid.buyer, bid.price));
+				debug_assert!(res.is_ok());
+			}
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			total_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(Vec::new())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 2, "storage version not updated");
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+	use frame_system::pallet_prelude::BlockNumberFor;
+
+	#[frame_support::storage_alias]
+	pub(super) type MarketIdsPerCloseBlock<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		BoundedVec<MarketId, CacheSize>,
+		ValueQuery,
+	>;
+
+	/// Moves the close schedule from one bounded vector per block to a double map with a
+	/// market counter per block.
+	pub struct MigrateCloseSchedule<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateCloseSchedule<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut total_weight = T::DbWeight::get().reads(1);
+
+			let on_chain_version = StorageVersion::get::<Pallet<T>>();
+			if on_chain_version != 2 {
+				return total_weight;
+			}
+
+			// Drain the old entries first, because they share the storage prefix with the new
+			// double map.
+			let schedule = MarketIdsPerCloseBlock::<T>::drain().collect::<alloc::vec::Vec<_>>();
+			for (block, market_ids) in schedule {
+				total_weight = total_weight.saturating_add(
+					T::DbWeight::get().reads_writes(1, market_ids.len() as u64 + 2),
+				);
+				if market_ids.is_empty() {
+					continue;
+				}
+				<MarketCountPerCloseBlock<T>>::insert(block, market_ids.len() as u32);
+				for market_id in market_ids {
+					crate::MarketIdsPerCloseBlock::<T>::insert(block, market_id, ());
+				}
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			total_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let scheduled = MarketIdsPerCloseBlock::<T>::iter_values()
+				.map(|market_ids| market_ids.len() as u32)
+				.sum::<u32>();
+			Ok(scheduled.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 3, "storage version not updated");
+			let scheduled: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				<MarketCountPerCloseBlock<T>>::iter_values().sum::<u32>() == scheduled,
+				"close schedule counters don't match"
+			);
+			ensure!(
+				crate::MarketIdsPerCloseBlock::<T>::iter_keys().count() as u32 == scheduled,
+				"close schedule entries don't match"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// Builds the account index of the outcome positions in the unsettled markets.
+	pub struct BackfillAccountPositions<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for BackfillAccountPositions<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut total_weight = T::DbWeight::get().reads(1);
+
+			let on_chain_version = StorageVersion::get::<Pallet<T>>();
+			if on_chain_version != 3 {
+				return total_weight;
+			}
+
+			for (market_id, market) in <Markets<T>>::iter() {
+				total_weight = total_weight.saturating_add(T::DbWeight::get().reads(2));
+				if matches!(market.status, MarketStatus::Redeemed | MarketStatus::Invalid) {
+					continue;
+				}
+				for outcome in <Outcomes<T>>::get(market_id).iter() {
+					if outcome.price.is_zero() {
+						continue;
+					}
+					<AccountPositions<T>>::insert(&outcome.owner, market_id, ());
+					total_weight = total_weight.saturating_add(T::DbWeight::get().writes(1));
+				}
+			}
+
+			StorageVersion::new(4).put::<Pallet<T>>();
+			total_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 4, "storage version not updated");
+			for (account, market_id) in <AccountPositions<T>>::iter_keys() {
+				ensure!(
+					<Outcomes<T>>::get(market_id)
+						.iter()
+						.any(|outcome| outcome.owner == account && !outcome.price.is_zero()),
+					"indexed position not found"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Initializes the counters of `Markets` and `Outcomes`, which became counted maps.
+	pub struct InitializeMarketCounters<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for InitializeMarketCounters<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut total_weight = T::DbWeight::get().reads(1);
+
+			let on_chain_version = StorageVersion::get::<Pallet<T>>();
+			if on_chain_version != 4 {
+				return total_weight;
+			}
+
+			let markets = <Markets<T>>::initialize_counter();
+			let outcomes = <Outcomes<T>>::initialize_counter();
+			total_weight = total_weight.saturating_add(
+				T::DbWeight::get().reads_writes(u64::from(markets) + u64::from(outcomes), 2),
+			);
+
+			StorageVersion::new(5).put::<Pallet<T>>();
+			total_weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(StorageVersion::get::<Pallet<T>>() == 5, "storage version not updated");
+			ensure!(
+				<Markets<T>>::count() as usize == <Markets<T>>::iter_keys().count(),
+				"market counter doesn't match"
+			);
+			ensure!(
+				<Outcomes<T>>::count() as usize == <Outcomes<T>>::iter_keys().count(),
+				"outcome counter doesn't match"
+			);
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use frame_support::assert_ok;
+
+	fn create_market() -> MarketId {
+		let market_id = TemplateModule::market_counter();
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			vec![[0; 32], [1; 32]].try_into().unwrap(),
+			System::block_number() + MIN_MARKET_PERIOD,
+			ALICE,
+			Default::default(),
+			Default::default(),
+		));
+		market_id
+	}
+
+	#[test]
+	fn v1_moves_the_funds_of_the_legacy_market_accounts() {
+		new_test_ext().execute_with(|| {
+			let funded = create_market();
+			let empty = create_market();
+			let legacy_account = TemplateModule::legacy_market_account(funded);
+			Balances::make_free_balance_be(&legacy_account, 500);
+			StorageVersion::new(0).put::<Pallet<Test>>();
+
+			v1::MigrateMarketAccounts::<Test>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 1);
+			assert_eq!(Balances::free_balance(legacy_account), 0);
+			assert_eq!(Balances::free_balance(TemplateModule::market_account(funded)), 500);
+			assert_eq!(Balances::free_balance(TemplateModule::market_account(empty)), 0);
+
+			// The migration only runs on the storage version 0.
+			Balances::make_free_balance_be(&TemplateModule::legacy_market_account(empty), 70);
+			v1::MigrateMarketAccounts::<Test>::on_runtime_upgrade();
+			assert_eq!(Balances::free_balance(TemplateModule::market_account(empty)), 0);
+		});
+	}
+
+	#[test]
+	fn v3_carries_over_the_close_schedule() {
+		new_test_ext().execute_with(|| {
+			let schedule = [(11, vec![0, 1]), (12, vec![2]), (13, vec![])];
+			for (block, market_ids) in schedule.clone() {
+				v3::MarketIdsPerCloseBlock::<Test>::insert(
+					block,
+					BoundedVec::<MarketId, CacheSize>::truncate_from(market_ids),
+				);
+			}
+			StorageVersion::new(2).put::<Pallet<Test>>();
+
+			#[cfg(feature = "try-runtime")]
+			let state = v3::MigrateCloseSchedule::<Test>::pre_upgrade().unwrap();
+			v3::MigrateCloseSchedule::<Test>::on_runtime_upgrade();
+			#[cfg(feature = "try-runtime")]
+			v3::MigrateCloseSchedule::<Test>::post_upgrade(state).unwrap();
+
+			assert_eq!(StorageVersion::get::<Pallet<Test>>(), 3);
+			for (block, market_ids) in schedule {
+				assert_eq!(<MarketCountPerCloseBlock<Test>>::get(block), market_ids.len() as u32);
+				let mut migrated = crate::MarketIdsPerCloseBlock::<Test>::iter_key_prefix(block)
+					.collect::<alloc::vec::Vec<_>>();
+				migrated.sort();
+				assert_eq!(migrated, market_ids);
+			}
+			assert!(!<MarketCountPerCloseBlock<Test>>::contains_key(13));
+			assert_eq!(crate::MarketIdsPerCloseBlock::<Test>::iter_keys().count(), 3);
+		});
+	}
+}
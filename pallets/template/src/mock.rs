@@ -1,14 +1,35 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{MarketId, OnParticipation, Participation};
+use core::cell::RefCell;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Currency, Hooks, IsInVec, OnUnbalanced},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use pallet_balances::NegativeImbalance;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Percent,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+pub const CREATOR_BOND: u64 = 100;
+pub const ORACLE_BOND: u64 = 50;
+pub const MIN_MARKET_PERIOD: u64 = 10;
+/// Receives the creation fees.
+pub const FEE_COLLECTOR: u64 = 99;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -17,6 +38,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		TemplateModule: pallet_template,
 	}
 );
@@ -39,7 +61,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,12 +70,132 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+parameter_types! {
+	pub const MarketsPalletId: PalletId = PalletId(*b"py/markt");
+	pub const ScalarOutlierTolerance: Percent = Percent::from_percent(10);
+	pub storage BatchBidClearing: bool = false;
+	pub storage CreationFee: u64 = 0;
+	pub storage InformationalEvents: bool = true;
+	pub FeeExemptAccounts: Vec<u64> = vec![CHARLIE];
+}
+
+/// Deposits the creation fees into `FEE_COLLECTOR`.
+pub struct FeeCollector;
+
+impl OnUnbalanced<NegativeImbalance<Test>> for FeeCollector {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<Test>) {
+		Balances::resolve_creating(&FEE_COLLECTOR, amount);
+	}
+}
+
+thread_local! {
+	pub static PARTICIPATIONS: RefCell<Vec<(u64, MarketId, Participation, u64)>> =
+		RefCell::new(Vec::new());
+}
+
+/// Records the participations in `PARTICIPATIONS`.
+pub struct ParticipationLog;
+
+impl OnParticipation<u64, u64> for ParticipationLog {
+	fn participation_recorded(
+		who: &u64,
+		market_id: MarketId,
+		participation: Participation,
+		amount: u64,
+	) {
+		PARTICIPATIONS.with(|log| log.borrow_mut().push((*who, market_id, participation, amount)));
+	}
+
+	fn participation_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type BatchBidClearing = BatchBidClearing;
+	type BidIncrement = ConstU64<1>;
+	type BidLogLength = ConstU32<4>;
+	type BondTopUpOrigin = EnsureRoot<u64>;
+	type Currency = Balances;
+	type CloseAnnouncementHorizon = ConstU64<3>;
+	type CreationFee = CreationFee;
+	type CreationFeeExempt = IsInVec<FeeExemptAccounts>;
+	type CreatorBond = ConstU64<CREATOR_BOND>;
+	type DataUpdateDeposit = ConstU64<10>;
+	type DestroyOrigin = EnsureRoot<u64>;
+	type FeeSchedule = pallet_template::DefaultFeeSchedule<ConstU64<5>>;
+	type InformationalEvents = InformationalEvents;
+	type MarketCreatorClearStorageTime = ConstU64<100>;
+	type MaxAttestationLength = ConstU32<256>;
+	type MaxBidsPerBlock = ConstU32<4>;
+	type MaxDataUpdates = ConstU32<2>;
+	type MaxManagers = ConstU32<2>;
+	type MaxMarketsPerBlock = ConstU32<2>;
+	type MaxOutcomes = ConstU32<32>;
+	type MaxPauseDuration = ConstU64<50>;
+	type MaxQueuedBids = ConstU32<4>;
+	type MaxScalarOracles = ConstU32<4>;
+	type MaxSponsors = ConstU32<16>;
+	type MinMarketPeriod = ConstU64<MIN_MARKET_PERIOD>;
+	type ModerationOrigin = EnsureRoot<u64>;
+	type OnCreationFee = FeeCollector;
+	type OnParticipation = ParticipationLog;
+	type OracleAcceptancePeriod = ConstU64<20>;
+	type OracleBond = ConstU64<ORACLE_BOND>;
+	type OracleGracePeriod = ConstU64<10>;
+	type PalletId = MarketsPalletId;
+	type ReportingPeriod = ConstU64<20>;
+	type ScalarOutlierTolerance = ScalarOutlierTolerance;
 	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	new_test_ext_with(Default::default())
+}
+
+/// Build genesis storage with the given genesis config of the pallet.
+pub fn new_test_ext_with(
+	genesis: pallet_template::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	genesis.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	// Go past genesis block so events get deposited
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		TemplateModule::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		TemplateModule::on_initialize(System::block_number());
+	}
 }
@@ -0,0 +1,157 @@
+//! Replays the extrinsic history of a market against a fresh mock runtime and compares the final
+//! market state with the state read from a chain. The accounts of the history have to be mapped
+//! to the accounts of the mock runtime first.
+
+use crate::{
+	mock::*, MarketId, MarketOf, Markets, Outcomes, OutcomesOf, SettlementEntry, SettlementReports,
+};
+use codec::{Decode, Encode};
+use frame_support::assert_ok;
+use sp_runtime::traits::Dispatchable;
+
+/// An extrinsic of the market history.
+#[derive(Decode, Encode, Clone, Debug, PartialEq, Eq)]
+pub struct ReplayStep {
+	/// The block, in which the extrinsic was included.
+	pub block: u64,
+	/// The signer of the extrinsic or `None` for a root call.
+	pub signer: Option<u64>,
+	pub call: RuntimeCall,
+	/// The extrinsic succeeded on chain.
+	pub succeeded: bool,
+}
+
+/// The state of a market, which is compared after the replay.
+#[derive(Decode, Encode, Clone, Debug, PartialEq, Eq)]
+pub struct MarketState {
+	pub market: Option<MarketOf<Test>>,
+	pub outcomes: OutcomesOf<Test>,
+	pub settlement_report: Option<Vec<SettlementEntry<u64, u64>>>,
+}
+
+impl MarketState {
+	pub fn of(market_id: MarketId) -> Self {
+		MarketState {
+			market: Markets::<Test>::get(market_id),
+			outcomes: Outcomes::<Test>::get(market_id),
+			settlement_report: SettlementReports::<Test>::get(market_id)
+				.map(|report| report.into_inner()),
+		}
+	}
+}
+
+/// Replays the SCALE encoded `Vec<ReplayStep>` and returns the final state of the market. Panics,
+/// if an extrinsic has another result than on chain.
+pub fn replay(market_id: MarketId, log: &[u8]) -> MarketState {
+	let steps = Vec::<ReplayStep>::decode(&mut &log[..]).expect("invalid replay log");
+	new_test_ext().execute_with(|| {
+		for (i, step) in steps.into_iter().enumerate() {
+			run_to_block(step.block);
+			let origin = match step.signer {
+				Some(signer) => RuntimeOrigin::signed(signer),
+				None => RuntimeOrigin::root(),
+			};
+			let res = step.call.dispatch(origin);
+			assert_eq!(
+				res.is_ok(),
+				step.succeeded,
+				"step {} diverged from the chain: {:?}",
+				i,
+				res
+			);
+		}
+		MarketState::of(market_id)
+	})
+}
+
+pub fn assert_replay_matches(market_id: MarketId, log: &[u8], on_chain: &MarketState) {
+	assert_eq!(replay(market_id, log), *on_chain);
+}
+
+fn step(block: u64, signer: u64, call: crate::Call<Test>, succeeded: bool) -> ReplayStep {
+	ReplayStep { block, signer: Some(signer), call: RuntimeCall::TemplateModule(call), succeeded }
+}
+
+// The first market id of a fresh chain.
+const MARKET_ID: MarketId = 1;
+
+#[test]
+fn replay_reproduces_the_settlement_of_a_market() {
+	let outcome_data = vec![[0; 32], [1; 32]];
+	let end = 1 + MIN_MARKET_PERIOD;
+	let on_chain = new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::market_counter(), MARKET_ID);
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			outcome_data.clone().try_into().unwrap(),
+			end,
+			ALICE,
+			Default::default(),
+			Default::default(),
+		));
+		assert_ok!(TemplateModule::buy_outcome(RuntimeOrigin::signed(BOB), MARKET_ID, 0, 100, 1));
+		run_to_block(2);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			MARKET_ID,
+			1,
+			50,
+			2
+		));
+		run_to_block(end);
+		assert_ok!(TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), MARKET_ID, 0));
+		run_to_block(end + 1);
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(CHARLIE), MARKET_ID));
+		MarketState::of(MARKET_ID)
+	});
+
+	let log = vec![
+		step(
+			1,
+			ALICE,
+			crate::Call::create_market {
+				data: [0; 32],
+				outcome_data: outcome_data.try_into().unwrap(),
+				end,
+				oracle: ALICE,
+				payout_ratios: Default::default(),
+				mechanism: Default::default(),
+			},
+			true,
+		),
+		step(
+			1,
+			BOB,
+			crate::Call::buy_outcome {
+				market_id: MARKET_ID,
+				outcome_index: 0,
+				price: 100,
+				valid_until: 1,
+			},
+			true,
+		),
+		step(
+			2,
+			CHARLIE,
+			crate::Call::buy_outcome {
+				market_id: MARKET_ID,
+				outcome_index: 1,
+				price: 50,
+				valid_until: 2,
+			},
+			true,
+		),
+		step(
+			end,
+			ALICE,
+			crate::Call::report_as_oracle { market_id: MARKET_ID, outcome_index: 0 },
+			true,
+		),
+		step(end + 1, CHARLIE, crate::Call::redeem { market_id: MARKET_ID }, true),
+		step(end + 1, BOB, crate::Call::redeem { market_id: MARKET_ID }, false),
+	];
+
+	assert!(on_chain.settlement_report.is_some());
+	assert_replay_matches(MARKET_ID, &log.encode(), &on_chain);
+}
@@ -1,27 +1,1975 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	mock::*, AnnouncedCloses, BidLogs, BidRecord, BondDisposition, CacheSize, ClosedSnapshots,
+	CreateMarketError, Error, Event, LocaleTag, MarketApi, MarketConstants,
+	MarketCountPerCloseBlock, MarketId, MarketParams, MarketStatus, MarketTransitions,
+	MarketTransitionsInBlock, Markets, MarketsCreatedInBlock, Mechanism, Outcomes, Participation,
+	PositionInfo, Resolution, SettlementEntry, MARKET_TRANSITIONS_DIGEST_PREFIX,
+};
+use codec::Decode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Get, Hooks, StorageInfoTrait},
+	weights::Weight,
+};
+use sp_runtime::{DigestItem, Perbill, Percent};
+
+fn create_market(creator: u64, outcome_amount: u8) -> MarketId {
+	let market_id = TemplateModule::market_counter();
+	let outcome_data = (0..outcome_amount).map(|i| [i; 32]).collect::<Vec<_>>();
+	assert_ok!(TemplateModule::create_market(
+		RuntimeOrigin::signed(creator),
+		[0; 32],
+		outcome_data.try_into().unwrap(),
+		System::block_number() + MIN_MARKET_PERIOD,
+		creator,
+		Default::default(),
+		Default::default(),
+	));
+	market_id
+}
+
+fn reported_market(winner_price: u64, loser_price: u64) -> MarketId {
+	let market_id = create_market(ALICE, 2);
+	let valid_until = System::block_number();
+	assert_ok!(TemplateModule::buy_outcome(
+		RuntimeOrigin::signed(BOB),
+		market_id,
+		0,
+		winner_price,
+		valid_until
+	));
+	assert_ok!(TemplateModule::buy_outcome(
+		RuntimeOrigin::signed(CHARLIE),
+		market_id,
+		1,
+		loser_price,
+		valid_until
+	));
+	run_to_block(Markets::<Test>::get(market_id).unwrap().end);
+	assert_ok!(TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), market_id, 0));
+	market_id
+}
+
+#[test]
+fn create_market_activates_and_counts() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Active);
+		assert_eq!(TemplateModule::market_counter(), market_id + 1);
+		System::assert_last_event(Event::MarketCreated { market_id, creator: ALICE }.into());
+	});
+}
+
+#[test]
+fn redeem_fails_for_unknown_market() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::redeem(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::MarketNotFound
+		);
+	});
+}
+
+#[test]
+fn redeem_pays_the_winner_once() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(CHARLIE), market_id));
+		System::assert_last_event(
+			Event::MarketRedeemed { market_id, winner_outcome: 0, winner: BOB }.into(),
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 50);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 50);
+
+		// Funds, which arrive at the market account later, can't trigger a second payout.
+		let market_account = TemplateModule::market_account(market_id);
+		Balances::make_free_balance_be(&market_account, 1_000);
+		assert_noop!(
+			TemplateModule::redeem(RuntimeOrigin::signed(CHARLIE), market_id),
+			Error::<Test>::MarketAlreadyRedeemed
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 50);
+	});
+}
+
+#[test]
+fn removed_markets_deposit_a_receipt() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id));
+		assert_ok!(TemplateModule::clear_storage(RuntimeOrigin::signed(ALICE), market_id));
+		System::assert_has_event(
+			Event::MarketRemoved {
+				market_id,
+				status: MarketStatus::Redeemed,
+				winner: Some(BOB),
+				total_volume: 150,
+				bond: BondDisposition::ReturnedToCreator,
+			}
+			.into(),
+		);
+
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			70,
+			valid_until
+		));
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), market_id));
+		System::assert_has_event(
+			Event::MarketRemoved {
+				market_id,
+				status: MarketStatus::Active,
+				winner: None,
+				total_volume: 70,
+				bond: BondDisposition::ReturnedToCreator,
+			}
+			.into(),
+		);
+
+		// The market waits for the acceptance of the oracle, so the creator can cancel it.
+		let market_id = TemplateModule::market_counter();
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			vec![[0; 32], [1; 32]].try_into().unwrap(),
+			System::block_number() + MIN_MARKET_PERIOD,
+			BOB,
+			Default::default(),
+			Default::default(),
+		));
+		assert_ok!(TemplateModule::cancel_market(RuntimeOrigin::signed(ALICE), market_id));
+		System::assert_has_event(
+			Event::MarketRemoved {
+				market_id,
+				status: MarketStatus::PendingOracle,
+				winner: None,
+				total_volume: 0,
+				bond: BondDisposition::ReturnedToCreator,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn redeem_fails_before_the_report() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_noop!(
+			TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::OutcomeNotReportedYet
+		);
+	});
+}
+
+#[test]
+fn redeem_fails_for_reported_market_resolved_as_invalid() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_ok!(<TemplateModule as MarketApi>::resolve_market(&market_id, Resolution::Invalid));
+
+		let market = Markets::<Test>::get(market_id).unwrap();
+		assert_eq!(market.status, MarketStatus::Invalid);
+		assert_eq!(market.oracle_outcome_report, Some(0));
+		assert_noop!(
+			TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::InvalidMarketStatus
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn disputed_market_keeps_its_funds_until_resolution() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_ok!(<TemplateModule as MarketApi>::dispute_market(&market_id));
+		System::assert_last_event(Event::MarketDisputed { market_id }.into());
+
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end + 1_000);
+		assert_noop!(
+			TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::MarketDisputed
+		);
+		assert_noop!(
+			TemplateModule::clear_storage(RuntimeOrigin::signed(ALICE), market_id),
+			Error::<Test>::InvalidMarketStatus
+		);
+		assert_noop!(
+			TemplateModule::destroy_market(RuntimeOrigin::root(), market_id),
+			Error::<Test>::MarketDisputed
+		);
+		assert_noop!(
+			TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB),
+			Error::<Test>::MarketNotActive
+		);
+		assert_ok!(TemplateModule::settle_markets(
+			RuntimeOrigin::signed(CHARLIE),
+			vec![market_id].try_into().unwrap()
+		));
+		System::assert_last_event(Event::MarketsSettled { settled: 0 }.into());
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 50);
+
+		assert_ok!(<TemplateModule as MarketApi>::resolve_market(
+			&market_id,
+			Resolution::Outcome(1)
+		));
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 100);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE + 100);
+	});
+}
+
+#[test]
+fn only_reported_markets_can_be_disputed() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_noop!(
+			<TemplateModule as MarketApi>::dispute_market(&market_id),
+			Error::<Test>::InvalidMarketStatus
+		);
+	});
+}
+
+#[test]
+fn market_status_matches_the_stored_market() {
+	new_test_ext().execute_with(|| {
+		assert!(!<TemplateModule as MarketApi>::market_exists(&0));
+		assert_eq!(<TemplateModule as MarketApi>::market_status(&0), None);
+
+		let market_id = reported_market(100, 50);
+		assert!(<TemplateModule as MarketApi>::market_exists(&market_id));
+		assert_eq!(
+			<TemplateModule as MarketApi>::market_status(&market_id),
+			Some(MarketStatus::Reported)
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn market_counters_stay_in_sync() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		let assert_counts = |markets: u32, closing_in_11: u32, closing_in_12: u32| {
+			assert_eq!(Markets::<Test>::count(), markets);
+			assert_eq!(Outcomes::<Test>::count(), markets);
+			assert_eq!(MarketCountPerCloseBlock::<Test>::get(11), closing_in_11);
+			assert_eq!(MarketCountPerCloseBlock::<Test>::get(12), closing_in_12);
+		};
+
+		let destroyed_on_idle = create_market(ALICE, 2);
+		let cancelled = TemplateModule::market_counter();
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			vec![[0; 32], [1; 32]].try_into().unwrap(),
+			System::block_number() + MIN_MARKET_PERIOD,
+			BOB,
+			Default::default(),
+			Default::default(),
+		));
+		run_to_block(2);
+		let destroyed_in_steps = create_market(BOB, 2);
+		assert_counts(3, 2, 1);
+
+		assert_ok!(TemplateModule::cancel_market(RuntimeOrigin::signed(ALICE), cancelled));
+		assert_counts(2, 1, 1);
+
+		// Destroying markets stay counted until their storage is removed.
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), destroyed_on_idle));
+		assert_counts(2, 0, 1);
+		assert_ok!(TemplateModule::continue_destroy(
+			RuntimeOrigin::signed(CHARLIE),
+			destroyed_on_idle,
+			1
+		));
+		assert_counts(2, 0, 1);
+		TemplateModule::on_idle(System::block_number(), Weight::MAX);
+		assert!(!Markets::<Test>::contains_key(destroyed_on_idle));
+		assert_counts(1, 0, 1);
+
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), destroyed_in_steps));
+		assert_ok!(TemplateModule::continue_destroy(
+			RuntimeOrigin::signed(CHARLIE),
+			destroyed_in_steps,
+			3
+		));
+		assert_counts(0, 0, 0);
+		assert!(!MarketCountPerCloseBlock::<Test>::contains_key(11));
+		assert!(!MarketCountPerCloseBlock::<Test>::contains_key(12));
+	});
+}
+
+#[test]
+fn destroyed_market_is_removed_in_steps() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			System::block_number()
+		));
+
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), market_id));
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Destroying);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		// Both outcomes are removed first, the rest of the market storage is the last item.
+		assert_ok!(TemplateModule::continue_destroy(RuntimeOrigin::signed(CHARLIE), market_id, 2));
+		assert!(Markets::<Test>::contains_key(market_id));
+		assert_ok!(TemplateModule::continue_destroy(RuntimeOrigin::signed(CHARLIE), market_id, 1));
+		assert!(!Markets::<Test>::contains_key(market_id));
+		System::assert_last_event(Event::MarketDestroyed { market_id }.into());
+
+		assert_noop!(
+			TemplateModule::continue_destroy(RuntimeOrigin::signed(CHARLIE), market_id, 1),
+			Error::<Test>::InvalidMarketStatus
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn market_creations_are_capped_per_block() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		create_market(ALICE, 2);
+		create_market(BOB, 2);
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			TemplateModule::create_market(
+				RuntimeOrigin::signed(CHARLIE),
+				[0; 32],
+				vec![[0; 32], [1; 32]].try_into().unwrap(),
+				System::block_number() + MIN_MARKET_PERIOD,
+				CHARLIE,
+				Default::default(),
+				Default::default(),
+			),
+			Error::<Test>::TooManyMarketsThisBlock
+		);
+		assert_eq!(
+			TemplateModule::validate_market_params(MarketParams {
+				creator: CHARLIE,
+				outcome_data: vec![[0; 32], [1; 32]],
+				end: System::block_number() + MIN_MARKET_PERIOD,
+				oracle: CHARLIE,
+				payout_ratios: vec![],
+				mechanism: Default::default(),
+			}),
+			Err(CreateMarketError::TooManyMarketsThisBlock)
+		);
+	});
+}
+
+#[test]
+fn validate_market_params_matches_create_market() {
+	new_test_ext().execute_with(|| {
+		let valid = MarketParams {
+			creator: ALICE,
+			outcome_data: vec![[0; 32], [1; 32]],
+			end: System::block_number() + MIN_MARKET_PERIOD,
+			oracle: ALICE,
+			payout_ratios: vec![],
+			mechanism: Mechanism::EnglishAuction,
+		};
+		let cases = [
+			(
+				MarketParams {
+					end: System::block_number() + MIN_MARKET_PERIOD - 1,
+					..valid.clone()
+				},
+				CreateMarketError::BelowMinMarketPeriod,
+			),
+			(
+				MarketParams { payout_ratios: vec![Percent::from_percent(50); 3], ..valid.clone() },
+				CreateMarketError::InvalidPayoutRatios,
+			),
+			(
+				MarketParams { outcome_data: vec![[0; 32], [0; 32]], ..valid.clone() },
+				CreateMarketError::DuplicateOutcomeData,
+			),
+			(
+				MarketParams { mechanism: Mechanism::Amm, ..valid.clone() },
+				CreateMarketError::MechanismNotSupported,
+			),
+		];
+		for (params, error) in cases {
+			assert_eq!(TemplateModule::validate_market_params(params.clone()), Err(error));
+			assert_noop!(
+				TemplateModule::create_market(
+					RuntimeOrigin::signed(params.creator),
+					[0; 32],
+					params.outcome_data.try_into().unwrap(),
+					params.end,
+					params.oracle,
+					params.payout_ratios.try_into().unwrap(),
+					params.mechanism,
+				),
+				Error::<Test>::from(error)
+			);
+		}
+
+		let market_id = TemplateModule::market_counter();
+		assert_eq!(TemplateModule::validate_market_params(valid), Ok(market_id));
+	});
+}
+
+#[test]
+fn genesis_creates_the_demo_markets() {
+	let demo_markets = vec![(ALICE, 2, MIN_MARKET_PERIOD), (BOB, 3, 20), (CHARLIE, 2, 20)];
+	let genesis =
+		crate::GenesisConfig::<Test> { demo_markets: demo_markets.clone(), ..Default::default() };
+	new_test_ext_with(genesis).execute_with(|| {
+		assert_eq!(Markets::<Test>::count(), 3);
+		for (market_id, (creator, outcome_amount, end)) in demo_markets.into_iter().enumerate() {
+			let market = Markets::<Test>::get(market_id as MarketId).unwrap();
+			assert_eq!(market.creator, creator);
+			assert_eq!(market.oracle, creator);
+			assert_eq!(market.end, end);
+			assert_eq!(market.status, MarketStatus::Active);
+			assert_eq!(Outcomes::<Test>::get(market_id as MarketId).len(), outcome_amount as usize);
+		}
+		assert_eq!(TemplateModule::markets_closing_in(20, 20)[0].1.len(), 2);
+
+		// The demo markets don't count towards the cap of the first block.
+		assert_eq!(MarketsCreatedInBlock::<Test>::get(), 0);
+		create_market(ALICE, 2);
+		create_market(BOB, 2);
+	});
+}
+
+#[test]
+fn market_constants_match_the_config() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			TemplateModule::market_constants(),
+			MarketConstants {
+				creator_bond: CREATOR_BOND,
+				oracle_bond: ORACLE_BOND,
+				keeper_tip: 5,
+				min_market_period: MIN_MARKET_PERIOD,
+				max_pause_duration: <Test as crate::Config>::MaxPauseDuration::get(),
+				oracle_acceptance_period: <Test as crate::Config>::OracleAcceptancePeriod::get(),
+				reporting_period: <Test as crate::Config>::ReportingPeriod::get(),
+				oracle_grace_period: <Test as crate::Config>::OracleGracePeriod::get(),
+				market_creator_clear_storage_time:
+					<Test as crate::Config>::MarketCreatorClearStorageTime::get(),
+				max_outcomes: <Test as crate::Config>::MaxOutcomes::get(),
+				max_bids_per_block: <Test as crate::Config>::MaxBidsPerBlock::get(),
+				max_queued_bids: <Test as crate::Config>::MaxQueuedBids::get(),
+				max_sponsors: <Test as crate::Config>::MaxSponsors::get(),
+				max_markets_per_close_block: CacheSize::get(),
+				bid_log_length: <Test as crate::Config>::BidLogLength::get(),
+				batch_bid_clearing: false,
+			}
+		);
+
+		BatchBidClearing::set(&true);
+		assert!(TemplateModule::market_constants().batch_bid_clearing);
+	});
+}
+
+#[test]
+fn market_creation_cap_is_reset_in_the_next_block() {
+	new_test_ext().execute_with(|| {
+		create_market(ALICE, 2);
+		create_market(BOB, 2);
+
+		run_to_block(System::block_number() + 1);
+		create_market(CHARLIE, 2);
+	});
+}
+
+#[test]
+fn closes_are_announced_within_the_horizon() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = System::block_number() + MIN_MARKET_PERIOD;
+
+		run_to_block(end - 3);
+		assert!(AnnouncedCloses::<Test>::get(end).is_empty());
+
+		run_to_block(end - 2);
+		let market_ids = AnnouncedCloses::<Test>::get(end);
+		assert_eq!(market_ids.clone().into_inner(), vec![market_id]);
+		System::assert_has_event(Event::MarketsToClose { close_block: end, market_ids }.into());
+
+		run_to_block(end);
+		assert!(AnnouncedCloses::<Test>::get(end).is_empty());
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Closed);
+	});
+}
+
+#[test]
+fn managers_act_for_the_creator_without_paying_its_deposits() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::add_market_manager(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			BOB
+		));
+		assert_noop!(
+			TemplateModule::add_market_manager(RuntimeOrigin::signed(BOB), market_id, CHARLIE),
+			Error::<Test>::CallerNotCreator
+		);
+
+		// Updates before the first bid are free.
+		assert_ok!(TemplateModule::update_market_data(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			[1; 32]
+		));
+
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		// The deposit is added to the creator bond, so only the creator can pay it.
+		assert_noop!(
+			TemplateModule::update_market_data(RuntimeOrigin::signed(BOB), market_id, [2; 32]),
+			Error::<Test>::CallerNotCreator
+		);
+		let reserved = Balances::reserved_balance(ALICE);
+		assert_ok!(TemplateModule::update_market_data(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			[2; 32]
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), reserved + 10);
+
+		assert_ok!(TemplateModule::remove_market_manager(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			BOB
+		));
+		assert_noop!(
+			TemplateModule::set_market_locale(RuntimeOrigin::signed(BOB), market_id, None),
+			Error::<Test>::CallerNotCreator
+		);
+	});
+}
+
+#[test]
+fn set_market_locale_moves_the_market_between_regions() {
+	new_test_ext().execute_with(|| {
+		let english = LocaleTag { language: *b"en", region: *b"GB" };
+		let german = LocaleTag { language: *b"de", region: *b"DE" };
+		let moved = create_market(ALICE, 2);
+		let other = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::set_market_locale(
+			RuntimeOrigin::signed(ALICE),
+			moved,
+			Some(english)
+		));
+		assert_ok!(TemplateModule::set_market_locale(
+			RuntimeOrigin::signed(ALICE),
+			other,
+			Some(german)
+		));
+		assert_eq!(TemplateModule::markets_in_region(*b"GB", None), vec![moved]);
+		assert_eq!(TemplateModule::markets_in_region(*b"DE", None), vec![other]);
+
+		let english_in_germany = LocaleTag { language: *b"en", region: *b"DE" };
+		assert_ok!(TemplateModule::set_market_locale(
+			RuntimeOrigin::signed(ALICE),
+			moved,
+			Some(english_in_germany)
+		));
+		System::assert_last_event(
+			Event::MarketLocaleSet { market_id: moved, locale: Some(english_in_germany) }.into(),
+		);
+		assert!(TemplateModule::markets_in_region(*b"GB", None).is_empty());
+		let mut in_germany = TemplateModule::markets_in_region(*b"DE", None);
+		in_germany.sort();
+		assert_eq!(in_germany, vec![moved, other]);
+		assert_eq!(TemplateModule::markets_in_region(*b"DE", Some(*b"en")), vec![moved]);
+
+		assert_ok!(TemplateModule::set_market_locale(RuntimeOrigin::signed(ALICE), moved, None));
+		assert_eq!(TemplateModule::markets_in_region(*b"DE", None), vec![other]);
+		assert!(TemplateModule::markets_in_region(*b"DE", Some(*b"en")).is_empty());
+	});
+}
+
+#[test]
+fn queued_bids_are_cleared_by_the_highest_price() {
+	new_test_ext().execute_with(|| {
+		BatchBidClearing::set(&true);
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		System::assert_last_event(
+			Event::BidQueued { market_id, outcome_index: 0, buyer: BOB, price: 100 }.into(),
+		);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			150,
+			valid_until
+		));
+		assert_eq!(Outcomes::<Test>::get(market_id)[0].price, 0);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 150);
+
+		run_to_block(2);
+		let outcome = &Outcomes::<Test>::get(market_id)[0];
+		assert_eq!((outcome.owner, outcome.price), (CHARLIE, 150));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 150);
+		System::assert_has_event(
+			Event::BidRefunded { market_id, outcome_index: 0, buyer: BOB, price: 100 }.into(),
+		);
+		System::assert_has_event(
+			Event::OutcomeBought { market_id, outcome_index: 0, buyer: CHARLIE }.into(),
+		);
+	});
+}
+
+#[test]
+fn queued_bids_with_equal_prices_are_won_by_the_earlier_bid() {
+	new_test_ext().execute_with(|| {
+		BatchBidClearing::set(&true);
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		for buyer in [BOB, CHARLIE] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(buyer),
+				market_id,
+				1,
+				100,
+				valid_until
+			));
+		}
+
+		run_to_block(2);
+		let outcome = &Outcomes::<Test>::get(market_id)[1];
+		assert_eq!((outcome.owner, outcome.price), (BOB, 100));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		System::assert_has_event(
+			Event::BidRefunded { market_id, outcome_index: 1, buyer: CHARLIE, price: 100 }.into(),
+		);
+	});
+}
+
+#[test]
+fn queued_bids_are_refunded_if_the_market_stopped_trading() {
+	new_test_ext().execute_with(|| {
+		BatchBidClearing::set(&true);
+		let paused = create_market(ALICE, 2);
+		let closed = create_market(BOB, 2);
+		run_to_block(2);
+		let destroyed = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		for market_id in [paused, closed, destroyed] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				market_id,
+				0,
+				100,
+				valid_until
+			));
+		}
+
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), paused));
+		Markets::<Test>::mutate(closed, |market| {
+			market.as_mut().unwrap().status = MarketStatus::Closed;
+		});
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), destroyed));
+
+		run_to_block(3);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		for market_id in [paused, closed, destroyed] {
+			assert_eq!(Outcomes::<Test>::get(market_id)[0].price, 0);
+			System::assert_has_event(
+				Event::BidRefunded { market_id, outcome_index: 0, buyer: CHARLIE, price: 100 }
+					.into(),
+			);
+		}
+	});
+}
+
+#[test]
+fn bids_beyond_the_queue_capacity_are_rejected() {
+	new_test_ext().execute_with(|| {
+		BatchBidClearing::set(&true);
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		for price in 1..=4 {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(BOB),
+				market_id,
+				0,
+				price,
+				valid_until
+			));
+		}
+		assert_noop!(
+			TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				market_id,
+				0,
+				5,
+				valid_until
+			),
+			Error::<Test>::BidQueueFull
+		);
+	});
+}
+
+#[test]
+fn create_market_rejects_unsupported_mechanisms() {
+	new_test_ext().execute_with(|| {
+		for mechanism in [Mechanism::Parimutuel, Mechanism::Amm, Mechanism::OrderBook] {
+			assert_noop!(
+				TemplateModule::create_market(
+					RuntimeOrigin::signed(ALICE),
+					[0; 32],
+					vec![[0; 32], [1; 32]].try_into().unwrap(),
+					System::block_number() + MIN_MARKET_PERIOD,
+					ALICE,
+					Default::default(),
+					mechanism,
+				),
+				Error::<Test>::MechanismNotSupported
+			);
+		}
+	});
+}
+
+#[test]
+fn create_market_rejects_duplicate_outcomes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_market(
+				RuntimeOrigin::signed(ALICE),
+				[0; 32],
+				vec![[0; 32], [1; 32], [0; 32]].try_into().unwrap(),
+				System::block_number() + MIN_MARKET_PERIOD,
+				ALICE,
+				Default::default(),
+				Default::default(),
+			),
+			Error::<Test>::DuplicateOutcomeData
+		);
+	});
+}
+
+#[test]
+fn fixed_owner_outcomes_cannot_be_outbid() {
+	new_test_ext().execute_with(|| {
+		let market_id = TemplateModule::market_counter();
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			vec![[0; 32], [1; 32]].try_into().unwrap(),
+			System::block_number() + MIN_MARKET_PERIOD,
+			ALICE,
+			Default::default(),
+			Mechanism::FixedOwner,
+		));
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		assert_noop!(
+			TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				market_id,
+				0,
+				200,
+				valid_until
+			),
+			Error::<Test>::OutcomeAlreadyOwned
+		);
+		assert_noop!(
+			TemplateModule::set_max_bid(RuntimeOrigin::signed(BOB), market_id, 0, Some(200)),
+			Error::<Test>::MechanismNotSupported
+		);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			1,
+			50,
+			valid_until
+		));
+		assert_eq!(Outcomes::<Test>::get(market_id)[0].owner, BOB);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+	});
+}
+
+#[test]
+fn creation_fee_is_taken_on_top_of_the_bonds_unless_exempt() {
+	new_test_ext().execute_with(|| {
+		CreationFee::set(&20);
+		create_market(ALICE, 2);
+		assert_eq!(Balances::reserved_balance(ALICE), CREATOR_BOND + ORACLE_BOND);
+		assert_eq!(
+			Balances::free_balance(ALICE),
+			INITIAL_BALANCE - CREATOR_BOND - ORACLE_BOND - 20
+		);
+		assert_eq!(Balances::free_balance(FEE_COLLECTOR), 20);
+
+		// CHARLIE is exempt from the creation fee.
+		create_market(CHARLIE, 2);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - CREATOR_BOND - ORACLE_BOND);
+		assert_eq!(Balances::free_balance(FEE_COLLECTOR), 20);
+	});
+}
+
+#[test]
+fn expired_bids_are_rejected_before_any_other_check() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		run_to_block(2);
+		// Neither the market nor the balance of the buyer are checked.
+		assert_noop!(
+			TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(BOB),
+				market_id + 1,
+				0,
+				INITIAL_BALANCE + 1,
+				1
+			),
+			Error::<Test>::BidExpired
+		);
+		assert_noop!(
+			TemplateModule::buy_outcome(RuntimeOrigin::signed(BOB), market_id, 0, 100, 1),
+			Error::<Test>::BidExpired
+		);
+	});
+}
+
+#[test]
+fn bids_are_valid_until_the_inclusive_block() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		run_to_block(2);
+		assert_ok!(TemplateModule::buy_outcome(RuntimeOrigin::signed(BOB), market_id, 0, 100, 2));
+		System::assert_last_event(
+			Event::OutcomeBought { market_id, outcome_index: 0, buyer: BOB }.into(),
+		);
+	});
+}
+
+#[test]
+fn twap_weights_the_probabilities_by_their_duration() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_eq!(TemplateModule::twap(market_id, 0, 0), None);
+		assert_eq!(TemplateModule::twap(market_id, 2, 10), None);
+		// Without bids all outcomes are equally likely.
+		assert_eq!(TemplateModule::twap(market_id, 0, 10), Some(Perbill::from_percent(50)));
+
+		run_to_block(2);
+		assert_ok!(TemplateModule::buy_outcome(RuntimeOrigin::signed(BOB), market_id, 0, 100, 2));
+		run_to_block(6);
+		// The bid at the start of the window isn't undone.
+		assert_eq!(TemplateModule::twap(market_id, 0, 4), Some(Perbill::from_percent(100)));
+		// 4 blocks at 100% and 1 block at 50%.
+		assert_eq!(TemplateModule::twap(market_id, 0, 5), Some(Perbill::from_percent(90)));
+
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			1,
+			300,
+			6
+		));
+		run_to_block(8);
+		// 2 blocks at 25% and 4 blocks at 100%.
+		assert_eq!(TemplateModule::twap(market_id, 0, 6), Some(Perbill::from_percent(75)));
+		// The window reaches back before the first bid, which counts 2 blocks at 50%.
+		assert_eq!(TemplateModule::twap(market_id, 0, 10), Some(Perbill::from_percent(55)));
+	});
+}
+
+#[test]
+fn settle_markets_performs_the_pending_step_of_each_market() {
+	new_test_ext().execute_with(|| {
+		let redeemed = reported_market(100, 50);
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), redeemed));
+		let redeemed_end = Markets::<Test>::get(redeemed).unwrap().end;
+
+		let expired = create_market(ALICE, 2);
+		run_to_block(Markets::<Test>::get(expired).unwrap().end + 1);
+		assert_eq!(Markets::<Test>::get(expired).unwrap().status, MarketStatus::Closed);
+
+		let paused = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), paused));
+		let overdue = create_market(ALICE, 2);
+
+		// `on_initialize` didn't run since, so the overdue market is still active.
+		System::set_block_number(redeemed_end + 101);
+		assert_ok!(TemplateModule::settle_markets(
+			RuntimeOrigin::signed(CHARLIE),
+			vec![paused, redeemed, 999, expired, overdue].try_into().unwrap()
+		));
+		System::assert_last_event(Event::MarketsSettled { settled: 3 }.into());
+
+		assert!(Markets::<Test>::get(redeemed).is_none());
+		assert_eq!(Markets::<Test>::get(expired).unwrap().status, MarketStatus::Invalid);
+		assert_eq!(Markets::<Test>::get(overdue).unwrap().status, MarketStatus::Closed);
+		System::assert_has_event(
+			Event::KeeperTipPaid { market_id: overdue, who: CHARLIE, amount: 5 }.into(),
+		);
+		// The failed close of the paused market doesn't roll back the others.
+		assert_eq!(Markets::<Test>::get(paused).unwrap().status, MarketStatus::Active);
+		assert!(TemplateModule::is_paused(paused));
+	});
+}
+
+#[test]
+fn transitions_of_the_block_are_deposited_as_digest() {
+	new_test_ext().execute_with(|| {
+		let unreported = create_market(ALICE, 2);
+		let redeemed = reported_market(100, 50);
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), redeemed));
+
+		let now = System::block_number();
+		TemplateModule::on_finalize(now);
+		let logs = System::digest()
+			.logs
+			.into_iter()
+			.filter_map(|item| match item {
+				DigestItem::Other(data) => Some(data),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(logs.len(), 1);
+		let (prefix, transitions) =
+			<([u8; 4], MarketTransitions)>::decode(&mut &logs[0][..]).unwrap();
+		assert_eq!(prefix, MARKET_TRANSITIONS_DIGEST_PREFIX);
+		let mut closed = transitions.closed.into_inner();
+		closed.sort();
+		assert_eq!(closed, vec![unreported, redeemed]);
+		assert_eq!(transitions.reported.into_inner(), vec![redeemed]);
+		assert_eq!(transitions.redeemed.into_inner(), vec![redeemed]);
+		assert!(transitions.invalid.is_empty());
+		assert!(!transitions.overflowed);
+
+		// The transitions are reset in the next block.
+		run_to_block(now + 1);
+		assert!(MarketTransitionsInBlock::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn closed_outcomes_are_frozen_at_the_close() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			System::block_number()
+		));
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end);
+		let snapshot = ClosedSnapshots::<Test>::get(market_id).unwrap();
+		assert_eq!(snapshot, Outcomes::<Test>::get(market_id));
+
+		assert_noop!(
+			TemplateModule::buy_outcome(RuntimeOrigin::signed(CHARLIE), market_id, 0, 200, end),
+			Error::<Test>::MarketNotActive
+		);
+		assert_noop!(
+			TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB),
+			Error::<Test>::MarketNotActive
+		);
+		// Later changes of the outcomes are ignored by the settlement.
+		Outcomes::<Test>::mutate(market_id, |outcomes| {
+			outcomes[0].owner = CHARLIE;
+			outcomes[0].price = 1_000;
+		});
+		assert_eq!(ClosedSnapshots::<Test>::get(market_id), Some(snapshot));
+
+		assert_ok!(TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), market_id, 0));
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(ALICE), market_id));
+		System::assert_last_event(
+			Event::MarketRedeemed { market_id, winner_outcome: 0, winner: BOB }.into(),
+		);
+		assert_eq!(
+			TemplateModule::settlement_report(market_id).unwrap(),
+			vec![SettlementEntry { account: BOB, paid_in: 100, paid_out: 100 }]
+		);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn redeem_stores_the_settlement_report() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_eq!(TemplateModule::settlement_report(market_id), None);
+
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(CHARLIE), market_id));
+		let report = TemplateModule::settlement_report(market_id).unwrap();
+		// The winner gets the whole volume, the creator bond isn't part of the pot.
+		assert_eq!(
+			report,
+			vec![
+				SettlementEntry { account: BOB, paid_in: 100, paid_out: 150 },
+				SettlementEntry { account: CHARLIE, paid_in: 50, paid_out: 0 },
+			]
+		);
+		let paid_in = report.iter().map(|entry| entry.paid_in).sum::<u64>();
+		let paid_out = report.iter().map(|entry| entry.paid_out).sum::<u64>();
+		assert_eq!(paid_in, 150);
+		assert_eq!(paid_out, paid_in);
+		assert_eq!(Balances::free_balance(TemplateModule::market_account(market_id)), 0);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().bond, CREATOR_BOND);
+		assert_eq!(Balances::reserved_balance(ALICE), CREATOR_BOND);
+	});
+}
+
+#[test]
+fn standing_order_rebids_until_its_maximum_price() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		assert_ok!(TemplateModule::set_max_bid(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			Some(200)
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 200);
+
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			150,
+			valid_until
+		));
+		System::assert_last_event(
+			Event::StandingOrderRebid { market_id, outcome_index: 0, owner: BOB, price: 151 }
+				.into(),
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 200);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+
+		// A bid at the maximum price wins and releases the standing order.
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			200,
+			valid_until
+		));
+		System::assert_last_event(
+			Event::OutcomeBought { market_id, outcome_index: 0, buyer: CHARLIE }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 200);
+	});
+}
+
+#[test]
+fn quotes_and_bid_logs_follow_standing_order_rebids() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		assert_ok!(TemplateModule::set_max_bid(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			Some(200)
+		));
+
+		let quote = TemplateModule::quote_buy(market_id, 0, &CHARLIE, 150).unwrap();
+		assert_eq!(quote.refund, None);
+		assert_eq!(quote.rebid, Some(151));
+		let quote = TemplateModule::quote_buy(market_id, 0, &CHARLIE, 200).unwrap();
+		assert_eq!(quote.refund, Some((BOB, 100)));
+		assert_eq!(quote.rebid, None);
+
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			150,
+			valid_until
+		));
+		// The outbid bid isn't logged, only the rebid of the standing order.
+		let block = System::block_number();
+		assert_eq!(
+			BidLogs::<Test>::get(market_id).into_inner(),
+			vec![
+				BidRecord { buyer: BOB, outcome_index: 0, price: 100, block },
+				BidRecord { buyer: BOB, outcome_index: 0, price: 151, block },
+			]
+		);
+	});
+}
+
+#[test]
+fn market_metrics_track_locked_funds_and_overdue_markets() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		let market_account = TemplateModule::market_account(market_id);
+
+		let metrics = TemplateModule::market_metrics();
+		assert_eq!(metrics.total_value_locked, 150 + Balances::total_balance(&market_account));
+		assert_eq!(metrics.bonds_reserved, Balances::reserved_balance(ALICE));
+		assert_eq!(metrics.past_redemption_deadline, 0);
+
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end + 101);
+		assert_eq!(TemplateModule::market_metrics().past_redemption_deadline, 1);
+		assert_eq!(TemplateModule::market_metrics().stuck_closed, 0);
+	});
+}
+
+#[test]
+fn all_storage_items_are_bounded() {
+	// Every storage item derives its full storage info, so the PoV size of each is bounded.
+	for info in TemplateModule::storage_info() {
+		assert!(
+			info.max_size.is_some(),
+			"{} has no maximum size",
+			String::from_utf8_lossy(&info.storage_name)
+		);
+	}
+}
+
+#[test]
+fn bids_are_rate_limited_per_account_and_block() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number() + 1;
+		for price in [10, 20, 30, 40] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(BOB),
+				market_id,
+				0,
+				price,
+				valid_until
+			));
+		}
+		assert_noop!(
+			TemplateModule::buy_outcome(RuntimeOrigin::signed(BOB), market_id, 0, 50, valid_until),
+			Error::<Test>::TooManyBidsInBlock
+		);
+		// The limit is per account.
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			50,
+			valid_until
+		));
+
+		run_to_block(System::block_number() + 1);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			60,
+			valid_until
+		));
+	});
+}
+
+fn ranked_market(payout_ratios: Vec<u8>) -> MarketId {
+	let market_id = TemplateModule::market_counter();
+	let outcome_data = vec![[0; 32], [1; 32], [2; 32]];
+	let payout_ratios = payout_ratios.into_iter().map(Percent::from_percent).collect::<Vec<_>>();
+	assert_ok!(TemplateModule::create_market(
+		RuntimeOrigin::signed(ALICE),
+		[0; 32],
+		outcome_data.try_into().unwrap(),
+		System::block_number() + MIN_MARKET_PERIOD,
+		ALICE,
+		payout_ratios.try_into().unwrap(),
+		Default::default(),
+	));
+	market_id
+}
+
+#[test]
+fn payout_ratios_split_the_pot_and_the_winner_gets_the_remainder() {
+	new_test_ext().execute_with(|| {
+		let market_id = ranked_market(vec![50, 33, 17]);
+		let valid_until = System::block_number();
+		for (buyer, outcome_index, price) in [(BOB, 0, 60), (CHARLIE, 1, 30), (ALICE, 2, 11)] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(buyer),
+				market_id,
+				outcome_index,
+				price,
+				valid_until
+			));
+		}
+		run_to_block(Markets::<Test>::get(market_id).unwrap().end);
+		assert_ok!(TemplateModule::report_ranking_as_oracle(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			vec![0, 1, 2].try_into().unwrap()
+		));
+
+		// 33% and 17% of 101 are rounded down, the winner gets the 51 left over.
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(CHARLIE), market_id));
+		System::assert_has_event(
+			Event::PositionPaidOut { market_id, outcome_index: 1, owner: CHARLIE, amount: 33 }
+				.into(),
+		);
+		System::assert_has_event(
+			Event::PositionPaidOut { market_id, outcome_index: 2, owner: ALICE, amount: 17 }.into(),
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 60 + 51);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 30 + 33);
+		assert_eq!(Balances::total_balance(&TemplateModule::market_account(market_id)), 0);
+	});
+}
+
+#[test]
+fn payout_ratios_must_sum_to_one_hundred_percent() {
+	new_test_ext().execute_with(|| {
+		for payout_ratios in [vec![50, 30, 10], vec![50, 30, 30], vec![50, 50, 0]] {
+			let payout_ratios =
+				payout_ratios.into_iter().map(Percent::from_percent).collect::<Vec<_>>();
+			assert_noop!(
+				TemplateModule::create_market(
+					RuntimeOrigin::signed(ALICE),
+					[0; 32],
+					vec![[0; 32], [1; 32], [2; 32]].try_into().unwrap(),
+					System::block_number() + MIN_MARKET_PERIOD,
+					ALICE,
+					payout_ratios.try_into().unwrap(),
+					Default::default(),
+				),
+				Error::<Test>::InvalidPayoutRatios
+			);
+		}
+	});
+}
+
+#[test]
+fn oracle_can_report_until_the_end_of_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(TemplateModule::reporting_deadline(end));
+		assert_noop!(
+			TemplateModule::resolve_expired_market(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::ReportingWindowNotExpired
+		);
+		assert_ok!(TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), market_id, 0));
+	});
+}
+
+#[test]
+fn oracle_cannot_report_after_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end);
+		System::set_block_number(TemplateModule::reporting_deadline(end) + 1);
+		assert_noop!(
+			TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), market_id, 0),
+			Error::<Test>::ReportingWindowExpired
+		);
+		assert_ok!(TemplateModule::resolve_expired_market(RuntimeOrigin::signed(BOB), market_id));
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Invalid);
+	});
+}
+
+#[test]
+fn markets_closing_in_includes_both_bounds_and_follows_the_end() {
+	new_test_ext().execute_with(|| {
+		let first = create_market(ALICE, 2);
+		run_to_block(2);
+		let second = create_market(ALICE, 2);
+		assert_eq!(TemplateModule::markets_closing_in(11, 11), vec![(11, vec![first])]);
+		assert_eq!(TemplateModule::markets_closing_in(12, 12), vec![(12, vec![second])]);
+		assert_eq!(
+			TemplateModule::markets_closing_in(11, 12),
+			vec![(11, vec![first]), (12, vec![second])]
+		);
+		assert!(TemplateModule::markets_closing_in(1, 10).is_empty());
+		assert!(TemplateModule::markets_closing_in(13, 100).is_empty());
+		assert!(TemplateModule::markets_closing_in(12, 11).is_empty());
+
+		// The paused duration postpones the close of the second market.
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), second));
+		run_to_block(5);
+		assert_ok!(TemplateModule::unpause_market(RuntimeOrigin::signed(ALICE), second, true));
+		assert_eq!(
+			TemplateModule::markets_closing_in(11, 15),
+			vec![(11, vec![first]), (15, vec![second])]
+		);
+		assert!(TemplateModule::markets_closing_in(12, 14).is_empty());
+
+		// Without the extension the close block stays.
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), first));
+		run_to_block(6);
+		assert_ok!(TemplateModule::unpause_market(RuntimeOrigin::signed(ALICE), first, false));
+		assert_eq!(TemplateModule::markets_closing_in(11, 11), vec![(11, vec![first])]);
+
+		// Closed markets leave the schedule.
+		run_to_block(11);
+		assert_eq!(TemplateModule::markets_closing_in(1, 100), vec![(15, vec![second])]);
+	});
+}
+
+#[test]
+fn paused_market_is_not_closed_and_its_end_can_be_extended() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), market_id));
+
+		run_to_block(end + 5);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Active);
+		assert_noop!(
+			TemplateModule::close_market(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::MarketPaused
+		);
+
+		// The market was paused for 15 blocks and closes 15 blocks after its original end.
+		assert_ok!(TemplateModule::unpause_market(RuntimeOrigin::signed(ALICE), market_id, true));
+		System::assert_last_event(
+			Event::MarketUnpaused { market_id, paused_for: 15, end: end + 15 }.into(),
+		);
+		run_to_block(end + 14);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Active);
+		run_to_block(end + 15);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Closed);
+	});
+}
+
+#[test]
+fn paused_market_past_its_end_is_closed_on_unpause() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		assert_ok!(TemplateModule::pause_market(RuntimeOrigin::signed(ALICE), market_id));
+
+		run_to_block(end + 5);
+		assert_ok!(TemplateModule::unpause_market(RuntimeOrigin::signed(ALICE), market_id, false));
+		System::assert_last_event(Event::MarketClosed { market_id }.into());
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Closed);
+	});
+}
+
+#[test]
+fn market_is_active_once_the_oracle_accepts() {
+	new_test_ext().execute_with(|| {
+		let market_id = TemplateModule::market_counter();
+		assert_ok!(TemplateModule::create_market(
+			RuntimeOrigin::signed(ALICE),
+			[0; 32],
+			vec![[0; 32], [1; 32]].try_into().unwrap(),
+			System::block_number() + MIN_MARKET_PERIOD,
+			BOB,
+			Default::default(),
+			Default::default(),
+		));
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::PendingOracle);
+		assert_noop!(
+			TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				market_id,
+				0,
+				10,
+				System::block_number()
+			),
+			Error::<Test>::MarketNotActive
+		);
+		assert_noop!(
+			TemplateModule::accept_oracle_role(RuntimeOrigin::signed(CHARLIE), market_id),
+			Error::<Test>::CallerNotOracle
+		);
+
+		assert_ok!(TemplateModule::accept_oracle_role(RuntimeOrigin::signed(BOB), market_id));
+		System::assert_last_event(Event::OracleAccepted { market_id, oracle: BOB }.into());
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Active);
+		assert_eq!(Balances::reserved_balance(BOB), ORACLE_BOND);
+		assert_eq!(Balances::reserved_balance(ALICE), CREATOR_BOND);
+	});
+}
+
+#[test]
+fn overdue_market_can_be_closed_by_anyone_for_the_keeper_tip() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		assert_noop!(
+			TemplateModule::close_market(RuntimeOrigin::signed(BOB), market_id),
+			Error::<Test>::MarketNotOverdue
+		);
+
+		// `on_initialize` didn't run for the close block.
+		System::set_block_number(end + 1);
+		assert_ok!(TemplateModule::close_market(RuntimeOrigin::signed(BOB), market_id));
+		System::assert_last_event(Event::KeeperTipPaid { market_id, who: BOB, amount: 5 }.into());
+		let market = Markets::<Test>::get(market_id).unwrap();
+		assert_eq!(market.status, MarketStatus::Closed);
+		assert_eq!(market.bond, CREATOR_BOND - 5);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 5);
+		assert_eq!(Balances::reserved_balance(ALICE), CREATOR_BOND - 5 + ORACLE_BOND);
+	});
+}
+
+#[test]
+fn sponsorship_is_paid_to_the_winner() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			System::block_number()
+		));
+		assert_ok!(TemplateModule::sponsor_market(RuntimeOrigin::signed(CHARLIE), market_id, 30));
+		System::assert_last_event(
+			Event::MarketSponsored { market_id, sponsor: CHARLIE, amount: 30 }.into(),
+		);
+
+		run_to_block(Markets::<Test>::get(market_id).unwrap().end);
+		assert_ok!(TemplateModule::report_as_oracle(RuntimeOrigin::signed(ALICE), market_id, 0));
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 30);
+	});
+}
+
+#[test]
+fn sponsorship_is_refunded_if_the_market_is_invalid() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::sponsor_market(RuntimeOrigin::signed(CHARLIE), market_id, 30));
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 30);
+
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(TemplateModule::reporting_deadline(end) + 1);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Invalid);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+	});
+}
+
+/// Resolves a scalar market over `0..100` with four buckets of width 25 to the reported values.
+fn resolve_scalar_market(reports: Vec<(u64, u128)>) -> MarketId {
+	let market_id = create_market(ALICE, 4);
+	let oracles = reports.iter().map(|(oracle, _)| *oracle).collect::<Vec<_>>();
+	assert_ok!(TemplateModule::configure_scalar_market(
+		RuntimeOrigin::signed(ALICE),
+		market_id,
+		oracles.try_into().unwrap(),
+		0,
+		100
+	));
+
+	let end = Markets::<Test>::get(market_id).unwrap().end;
+	run_to_block(end);
+	for (oracle, value) in reports {
+		Balances::make_free_balance_be(&oracle, INITIAL_BALANCE);
+		assert_ok!(TemplateModule::report_scalar(RuntimeOrigin::signed(oracle), market_id, value));
+	}
+	run_to_block(TemplateModule::reporting_deadline(end) + 1);
+	assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Reported);
+	market_id
+}
+
+#[test]
+fn scalar_market_resolves_to_the_median_of_an_odd_number_of_reports() {
+	new_test_ext().execute_with(|| {
+		let market_id = resolve_scalar_market(vec![(4, 32), (5, 10), (6, 30)]);
+		System::assert_has_event(
+			Event::ScalarMarketResolved { market_id, median: 30, outcome_index: 1 }.into(),
+		);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().oracle_outcome_report, Some(1));
+
+		// The tolerance is 10% of the range, so only the report of 10 is an outlier.
+		System::assert_has_event(
+			Event::ScalarReporterSlashed { market_id, oracle: 5, amount: ORACLE_BOND }.into(),
+		);
+		assert_eq!(Balances::total_balance(&5), INITIAL_BALANCE - ORACLE_BOND);
+		for oracle in [4, 6] {
+			assert_eq!(Balances::free_balance(oracle), INITIAL_BALANCE);
+		}
+	});
+}
+
+#[test]
+fn scalar_market_resolves_to_the_lower_median_of_an_even_number_of_reports() {
+	new_test_ext().execute_with(|| {
+		// The upper median and the mean of the two middle values fall into the third bucket.
+		let market_id = resolve_scalar_market(vec![(4, 55), (5, 35), (6, 45), (7, 60)]);
+		System::assert_has_event(
+			Event::ScalarMarketResolved { market_id, median: 45, outcome_index: 1 }.into(),
+		);
+
+		// Reports exactly at the tolerance of 10 aren't slashed.
+		System::assert_has_event(
+			Event::ScalarReporterSlashed { market_id, oracle: 7, amount: ORACLE_BOND }.into(),
+		);
+		assert_eq!(Balances::total_balance(&7), INITIAL_BALANCE - ORACLE_BOND);
+		for oracle in [4, 5, 6] {
+			assert_eq!(Balances::free_balance(oracle), INITIAL_BALANCE);
+			assert_eq!(Balances::reserved_balance(oracle), 0);
+		}
+	});
+}
+
+#[test]
+fn account_positions_show_escrowed_and_claimable_amounts() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_eq!(
+			TemplateModule::account_positions(BOB),
+			vec![PositionInfo {
+				market_id,
+				outcome_index: 0,
+				status: MarketStatus::Reported,
+				escrowed: 100,
+				claimable: 150,
+			}]
+		);
+		assert_eq!(
+			TemplateModule::account_positions(CHARLIE),
+			vec![PositionInfo {
+				market_id,
+				outcome_index: 1,
+				status: MarketStatus::Reported,
+				escrowed: 50,
+				claimable: 0,
+			}]
+		);
+
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id));
+		assert!(TemplateModule::account_positions(BOB).is_empty());
+		assert!(TemplateModule::account_positions(CHARLIE).is_empty());
+	});
+}
+
+#[test]
+fn outbid_account_loses_its_position() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		assert_eq!(TemplateModule::account_positions(BOB).len(), 1);
+
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			0,
+			120,
+			valid_until
+		));
+		assert!(TemplateModule::account_positions(BOB).is_empty());
+		assert_eq!(TemplateModule::account_positions(CHARLIE)[0].escrowed, 120);
+	});
+}
+
+#[test]
+fn moderation_origin_refunds_a_single_participant() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 3);
+		let valid_until = System::block_number();
+		for (buyer, outcome_index, price) in [(BOB, 0, 100), (BOB, 1, 50), (CHARLIE, 2, 30)] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(buyer),
+				market_id,
+				outcome_index,
+				price,
+				valid_until
+			));
+		}
+		assert_noop!(
+			TemplateModule::force_refund_participant(RuntimeOrigin::signed(ALICE), market_id, BOB),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB));
+		System::assert_last_event(
+			Event::ParticipantRefunded { market_id, who: BOB, amount: 150 }.into(),
+		);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(TemplateModule::account_positions(BOB).is_empty());
+		let outcomes = crate::Outcomes::<Test>::get(market_id);
+		assert!(outcomes[..2].iter().all(|outcome| outcome.owner == ALICE && outcome.price == 0));
+		assert_eq!(outcomes[2].owner, CHARLIE);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 30);
+		assert!(BidLogs::<Test>::get(market_id).iter().all(|record| record.buyer == CHARLIE));
+
+		assert_noop!(
+			TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB),
+			Error::<Test>::NothingToRefund
+		);
+	});
+}
+
+#[test]
+fn forced_refunds_drop_the_queued_bids_of_the_participant() {
+	new_test_ext().execute_with(|| {
+		BatchBidClearing::set(&true);
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		for (buyer, price) in [(BOB, 100), (CHARLIE, 50)] {
+			assert_ok!(TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(buyer),
+				market_id,
+				0,
+				price,
+				valid_until
+			));
+		}
+
+		assert_ok!(TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB));
+		System::assert_last_event(
+			Event::ParticipantRefunded { market_id, who: BOB, amount: 100 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		// The bid of BOB is gone, so the lower bid wins the clearing.
+		run_to_block(2);
+		let outcome = &Outcomes::<Test>::get(market_id)[0];
+		assert_eq!((outcome.owner, outcome.price), (CHARLIE, 50));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn markets_with_a_large_pot_must_top_up_an_outdated_bond() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let quiet_market_id = create_market(ALICE, 2);
+		// Both markets were created with a bond below the current `CreatorBond`.
+		for id in [market_id, quiet_market_id] {
+			Markets::<Test>::mutate(id, |market| market.as_mut().unwrap().bond = CREATOR_BOND - 40);
+			Balances::unreserve(&ALICE, 40);
+		}
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			200,
+			valid_until
+		));
+
+		assert_ok!(TemplateModule::require_bond_top_up(
+			RuntimeOrigin::root(),
+			vec![market_id, quiet_market_id].try_into().unwrap(),
+			100
+		));
+		System::assert_last_event(
+			Event::BondTopUpRequired { market_id, required: CREATOR_BOND }.into(),
+		);
+		assert_noop!(
+			TemplateModule::buy_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				market_id,
+				1,
+				50,
+				valid_until
+			),
+			Error::<Test>::BondTopUpRequired
+		);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			quiet_market_id,
+			1,
+			50,
+			valid_until
+		));
+
+		let reserved = Balances::reserved_balance(ALICE);
+		assert_ok!(TemplateModule::top_up_bond(RuntimeOrigin::signed(ALICE), market_id));
+		System::assert_last_event(Event::BondToppedUp { market_id, amount: 40 }.into());
+		assert_eq!(Balances::reserved_balance(ALICE), reserved + 40);
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().bond, CREATOR_BOND);
+		assert_noop!(
+			TemplateModule::top_up_bond(RuntimeOrigin::signed(ALICE), market_id),
+			Error::<Test>::BondSufficient
+		);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(CHARLIE),
+			market_id,
+			1,
+			50,
+			valid_until
+		));
+	});
+}
+
+#[test]
+fn participations_are_reported_on_bids_and_settlements() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_eq!(
+			PARTICIPATIONS.with(|log| log.borrow().clone()),
+			vec![
+				(BOB, market_id, Participation::Bid, 100),
+				(CHARLIE, market_id, Participation::Bid, 50),
+			]
+		);
+
+		assert_ok!(TemplateModule::redeem(RuntimeOrigin::signed(BOB), market_id));
+		let settlements = PARTICIPATIONS.with(|log| {
+			log.borrow()
+				.iter()
+				.filter(|(_, id, participation, _)| {
+					*id == market_id && *participation == Participation::Settlement
+				})
+				.map(|(who, _, _, amount)| (*who, *amount))
+				.collect::<Vec<_>>()
+		});
+		assert!(settlements.contains(&(BOB, 150)));
+		assert!(settlements.contains(&(CHARLIE, 0)));
+	});
+}
+
+#[test]
+fn creator_cannot_retire_an_outcome_traded_before_a_refund() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 2);
+		let valid_until = System::block_number();
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			valid_until
+		));
+		assert_ok!(TemplateModule::force_refund_participant(RuntimeOrigin::root(), market_id, BOB));
+
+		assert_noop!(
+			TemplateModule::retire_outcome(RuntimeOrigin::signed(ALICE), market_id, 0),
+			Error::<Test>::OutcomeAlreadyTraded
+		);
+		assert_ok!(TemplateModule::retire_outcome(RuntimeOrigin::signed(ALICE), market_id, 1));
+		assert_ok!(TemplateModule::retire_outcome(RuntimeOrigin::root(), market_id, 0));
+	});
+}
+
+#[test]
+fn scalar_market_with_the_median_in_a_retired_outcome_is_invalid() {
+	new_test_ext().execute_with(|| {
+		let market_id = create_market(ALICE, 4);
+		assert_ok!(TemplateModule::configure_scalar_market(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			vec![4, 5].try_into().unwrap(),
+			0,
+			100
+		));
+		assert_ok!(TemplateModule::retire_outcome(RuntimeOrigin::signed(ALICE), market_id, 1));
+
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end);
+		for (oracle, value) in [(4, 30), (5, 32)] {
+			Balances::make_free_balance_be(&oracle, INITIAL_BALANCE);
+			assert_ok!(TemplateModule::report_scalar(
+				RuntimeOrigin::signed(oracle),
+				market_id,
+				value
+			));
+		}
+		run_to_block(TemplateModule::reporting_deadline(end) + 1);
+
+		assert_eq!(Markets::<Test>::get(market_id).unwrap().status, MarketStatus::Invalid);
+		for oracle in [4, 5] {
+			assert_eq!(Balances::reserved_balance(oracle), 0);
+		}
+	});
+}
+
+#[test]
+fn external_resolution_clears_the_attestation_of_the_oracle() {
+	new_test_ext().execute_with(|| {
+		let market_id = reported_market(100, 50);
+		assert_ok!(TemplateModule::attest_resolution(
+			RuntimeOrigin::signed(ALICE),
+			market_id,
+			b"report".to_vec().try_into().unwrap()
+		));
+		assert!(crate::ResolutionAttestations::<Test>::contains_key(market_id));
+
+		assert_ok!(<TemplateModule as MarketApi>::dispute_market(&market_id));
+		assert_ok!(<TemplateModule as MarketApi>::resolve_market(
+			&market_id,
+			Resolution::Outcome(1)
+		));
+		assert!(!crate::ResolutionAttestations::<Test>::contains_key(market_id));
+
+		assert_ok!(<TemplateModule as MarketApi>::attest_resolution(
+			&market_id,
+			CHARLIE,
+			b"committee".to_vec()
+		));
+		assert_eq!(crate::ResolutionAttestations::<Test>::get(market_id).unwrap().0, CHARLIE);
+	});
+}
+
+#[test]
+fn destroyed_markets_are_removed_from_the_schedules() {
+	new_test_ext().execute_with(|| {
+		let active_market_id = create_market(ALICE, 2);
+		let closed_market_id = create_market(BOB, 2);
+		let end = Markets::<Test>::get(active_market_id).unwrap().end;
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), active_market_id));
+		assert!(!crate::MarketIdsPerCloseBlock::<Test>::contains_key(end, active_market_id));
+		assert_eq!(crate::MarketCountPerCloseBlock::<Test>::get(end), 1);
+
+		run_to_block(end);
+		assert_eq!(Markets::<Test>::get(closed_market_id).unwrap().status, MarketStatus::Closed);
+		let expiry = TemplateModule::reporting_deadline(end) + 1;
+		assert!(crate::MarketIdsPerReportDeadline::<Test>::contains_key(expiry, closed_market_id));
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), closed_market_id));
+		assert!(!crate::MarketIdsPerReportDeadline::<Test>::contains_key(expiry, closed_market_id));
+		assert!(!crate::MarketCountPerReportDeadline::<Test>::contains_key(expiry));
+	});
+}
+
+#[test]
+fn informational_events_can_be_disabled() {
+	new_test_ext().execute_with(|| {
+		InformationalEvents::set(&false);
+		let market_id = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::buy_outcome(
+			RuntimeOrigin::signed(BOB),
+			market_id,
+			0,
+			100,
+			System::block_number()
+		));
+		let destroyed = create_market(ALICE, 2);
+		assert_ok!(TemplateModule::destroy_market(RuntimeOrigin::root(), destroyed));
+
+		System::reset_events();
+		// Only the destruction of the market consumes weight.
+		let weight = TemplateModule::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(weight, <() as crate::WeightInfo>::continue_destroy(3));
+		assert!(!Markets::<Test>::contains_key(destroyed));
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::TemplateModule(Event::HighestOutcome { .. })
+		)));
+
+		// The close is still announced in storage, but without an event.
+		let end = Markets::<Test>::get(market_id).unwrap().end;
+		run_to_block(end - 2);
+		assert_eq!(AnnouncedCloses::<Test>::get(end).into_inner(), vec![market_id]);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::TemplateModule(Event::MarketsToClose { .. })
+		)));
+	});
+}
+
+#[test]
+fn highest_outcomes_are_sampled_from_the_parent_hash() {
+	new_test_ext().execute_with(|| {
+		for block in 1..=3 {
+			run_to_block(block);
+			for creator in [ALICE, BOB] {
+				let market_id = create_market(creator, 3);
+				assert_ok!(TemplateModule::buy_outcome(
+					RuntimeOrigin::signed(CHARLIE),
+					market_id,
+					(market_id % 3) as u8,
+					10,
+					block
+				));
+			}
+		}
+		let highest_outcomes = || {
+			System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					RuntimeEvent::TemplateModule(Event::HighestOutcome {
+						market_id,
+						highest_outcome,
+						seed,
+					}) => Some((market_id, highest_outcome, seed)),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+		let sample = |seed: sp_core::H256| {
+			System::reset_events();
+			System::set_parent_hash(seed);
+			TemplateModule::emit_highest_outcomes(2);
+			highest_outcomes()
+		};
+
+		let seed = sp_core::H256::repeat_byte(1);
+		let picks = sample(seed);
+		assert_eq!(picks.len(), 2);
+		assert_ne!(picks[0].0, picks[1].0);
+		for (market_id, highest_outcome, event_seed) in &picks {
+			assert_eq!(*highest_outcome, Some((market_id % 3) as u8));
+			assert_eq!(*event_seed, seed);
+		}
+		// The seed of the events reproduces the picks.
+		assert_eq!(sample(seed), picks);
+
+		// The proof size limits the number of events as well.
+		System::reset_events();
+		let unit = <() as crate::WeightInfo>::emit_highest_outcomes(1);
+		TemplateModule::on_idle(3, Weight::from_parts(u64::MAX, unit.proof_size() * 2));
+		assert_eq!(highest_outcomes().len(), 2);
+	});
+}
+
+#[test]
+fn on_idle_returns_the_consumed_weight() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::on_idle(1, Weight::MAX), Weight::zero());
+
+		create_market(ALICE, 2);
+		assert_eq!(
+			TemplateModule::on_idle(1, Weight::MAX),
+			<() as crate::WeightInfo>::emit_highest_outcomes(1)
 		);
 	});
 }
@@ -1,30 +1,7 @@
-
-//! Autogenerated weights for pallet_template
+//! Placeholder weights for pallet_template
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_template
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// pallets/template/src/weights.rs
-// --template
-// ../../.maintain/frame-weight-template.hbs
+//! THE VALUES IN THIS FILE ARE ESTIMATES AND NOT THE OUTPUT OF THE BENCHMARK CLI. THEY MUST BE
+//! REPLACED BY RUNNING `benchmark pallet --pallet pallet_template` ON REFERENCE HARDWARE.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -35,31 +12,272 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_template.
 pub trait WeightInfo {
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+	fn create_market(o: u32, ) -> Weight;
+	fn destroy_market(o: u32, ) -> Weight;
+	fn on_initialize(m: u32, b: u32, a: u32, ) -> Weight;
+	fn on_finalize() -> Weight;
+	fn redeem(o: u32, ) -> Weight;
+	fn clear_storage_repatriate() -> Weight;
+	fn clear_storage_unreserve() -> Weight;
+	fn report_as_oracle() -> Weight;
+	fn resolve_expired_market_invalid(o: u32, ) -> Weight;
+	fn resolve_expired_market_scalar(r: u32, ) -> Weight;
+	fn continue_destroy(i: u32, ) -> Weight;
+	fn buy_outcome() -> Weight;
+	fn report_ranking_as_oracle(o: u32, ) -> Weight;
+	fn accept_oracle_role() -> Weight;
+	fn replace_oracle() -> Weight;
+	fn cancel_market() -> Weight;
+	fn pause_market() -> Weight;
+	fn unpause_market() -> Weight;
+	fn close_market() -> Weight;
+	fn configure_scalar_market() -> Weight;
+	fn report_scalar() -> Weight;
+	fn sponsor_market() -> Weight;
+	fn update_market_data() -> Weight;
+	fn set_market_locale() -> Weight;
+	fn force_refund_participant() -> Weight;
+	fn top_up_bond() -> Weight;
+	fn require_bond_top_up(m: u32, ) -> Weight;
+	fn retire_outcome() -> Weight;
+	fn attest_resolution() -> Weight;
+	fn set_max_bid() -> Weight;
+	fn add_market_manager() -> Weight;
+	fn remove_market_manager() -> Weight;
+	fn emit_highest_outcomes(c: u32, ) -> Weight;
+	fn resolve_market_outcome() -> Weight;
+	fn resolve_market_invalid(o: u32, ) -> Weight;
+	fn dispute_market() -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Placeholder weights for pallet_template, see the module documentation.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+	/// The range of component `o` is `[1, 32]`.
+	fn create_market(o: u32, ) -> Weight {
+		Weight::from_parts(30_211_000, 16751)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn destroy_market(o: u32, ) -> Weight {
+		Weight::from_parts(22_104_000, 7698)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// The range of component `m` is `[0, 64]`.
+	/// The range of component `b` is `[0, 256]`.
+	/// The range of component `a` is `[0, 1000]`.
+	fn on_initialize(m: u32, b: u32, a: u32, ) -> Weight {
+		Weight::from_parts(15_120_000, 19368)
+			.saturating_add(Weight::from_parts(6_914_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(21_310_000, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(1_052_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2640).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 6229).saturating_mul(b.into()))
+	}
+	fn on_finalize() -> Weight {
+		Weight::from_parts(43_000_000, 22599)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn redeem(o: u32, ) -> Weight {
+		Weight::from_parts(48_374_000, 26841)
+			.saturating_add(Weight::from_parts(27_518_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 6327).saturating_mul(o.into()))
+	}
+	fn clear_storage_repatriate() -> Weight {
+		Weight::from_parts(39_000_000, 7846)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn clear_storage_unreserve() -> Weight {
+		Weight::from_parts(32_000_000, 5243)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	fn report_as_oracle() -> Weight {
+		Weight::from_parts(35_000_000, 20277)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn resolve_expired_market_invalid(o: u32, ) -> Weight {
+		Weight::from_parts(46_120_000, 24326)
+			.saturating_add(Weight::from_parts(14_305_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 3724).saturating_mul(o.into()))
+	}
+	/// The range of component `r` is `[1, 16]`.
+	fn resolve_expired_market_scalar(r: u32, ) -> Weight {
+		Weight::from_parts(38_772_000, 21530)
+			.saturating_add(Weight::from_parts(11_893_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// The range of component `i` is `[1, 33]`.
+	fn continue_destroy(i: u32, ) -> Weight {
+		Weight::from_parts(9_512_000, 6048)
+			.saturating_add(Weight::from_parts(2_134_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(i.into())))
+	}
+	fn buy_outcome() -> Weight {
+		Weight::from_parts(64_000_000, 15304)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn report_ranking_as_oracle(o: u32, ) -> Weight {
+		Weight::from_parts(37_406_000, 20310)
+			.saturating_add(Weight::from_parts(118_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn accept_oracle_role() -> Weight {
+		Weight::from_parts(29_000_000, 7754)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn replace_oracle() -> Weight {
+		Weight::from_parts(21_000_000, 8216)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn cancel_market() -> Weight {
+		Weight::from_parts(48_000_000, 16940)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+	}
+	fn pause_market() -> Weight {
+		Weight::from_parts(19_000_000, 8216)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	fn unpause_market() -> Weight {
+		Weight::from_parts(33_000_000, 13674)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	fn close_market() -> Weight {
+		Weight::from_parts(96_000_000, 152830)
+			.saturating_add(T::DbWeight::get().reads(73_u64))
+			.saturating_add(T::DbWeight::get().writes(74_u64))
+	}
+	fn configure_scalar_market() -> Weight {
+		Weight::from_parts(24_000_000, 13274)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn report_scalar() -> Weight {
+		Weight::from_parts(33_000_000, 11508)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn sponsor_market() -> Weight {
+		Weight::from_parts(38_000_000, 11109)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn update_market_data() -> Weight {
+		Weight::from_parts(31_000_000, 16244)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_market_locale() -> Weight {
+		Weight::from_parts(24_000_000, 10731)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn force_refund_participant() -> Weight {
+		Weight::from_parts(214_000_000, 91320)
+			.saturating_add(T::DbWeight::get().reads(36_u64))
+			.saturating_add(T::DbWeight::get().writes(36_u64))
+	}
+	fn top_up_bond() -> Weight {
+		Weight::from_parts(27_000_000, 7659)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// The range of component `m` is `[1, 32]`.
+	fn require_bond_top_up(m: u32, ) -> Weight {
+		Weight::from_parts(7_812_000, 0)
+			.saturating_add(Weight::from_parts(21_406_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 10301).saturating_mul(m.into()))
+	}
+	fn retire_outcome() -> Weight {
+		Weight::from_parts(46_000_000, 18262)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn attest_resolution() -> Weight {
+		Weight::from_parts(22_000_000, 6431)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_max_bid() -> Weight {
+		Weight::from_parts(38_000_000, 14872)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn add_market_manager() -> Weight {
+		Weight::from_parts(19_000_000, 6096)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn remove_market_manager() -> Weight {
+		Weight::from_parts(19_000_000, 6096)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `c` is `[1, 50]`.
+	fn emit_highest_outcomes(c: u32, ) -> Weight {
+		Weight::from_parts(6_104_000, 3000)
+			.saturating_add(Weight::from_parts(14_912_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 11102).saturating_mul(c.into()))
+	}
+	fn resolve_market_outcome() -> Weight {
+		Weight::from_parts(148_000_000, 58960)
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(24_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn resolve_market_invalid(o: u32, ) -> Weight {
+		Weight::from_parts(47_920_000, 24418)
+			.saturating_add(Weight::from_parts(14_190_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 3724).saturating_mul(o.into()))
+	}
+	fn dispute_market() -> Weight {
+		Weight::from_parts(15_000_000, 3630)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
@@ -67,24 +285,231 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+	/// The range of component `o` is `[1, 32]`.
+	fn create_market(o: u32, ) -> Weight {
+		Weight::from_parts(30_211_000, 16751)
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn destroy_market(o: u32, ) -> Weight {
+		Weight::from_parts(22_104_000, 7698)
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// The range of component `m` is `[0, 64]`.
+	/// The range of component `b` is `[0, 256]`.
+	/// The range of component `a` is `[0, 1000]`.
+	fn on_initialize(m: u32, b: u32, a: u32, ) -> Weight {
+		Weight::from_parts(15_120_000, 19368)
+			.saturating_add(Weight::from_parts(6_914_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(21_310_000, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(1_052_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2640).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 6229).saturating_mul(b.into()))
+	}
+	fn on_finalize() -> Weight {
+		Weight::from_parts(43_000_000, 22599)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn redeem(o: u32, ) -> Weight {
+		Weight::from_parts(48_374_000, 26841)
+			.saturating_add(Weight::from_parts(27_518_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 6327).saturating_mul(o.into()))
+	}
+	fn clear_storage_repatriate() -> Weight {
+		Weight::from_parts(39_000_000, 7846)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn clear_storage_unreserve() -> Weight {
+		Weight::from_parts(32_000_000, 5243)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn report_as_oracle() -> Weight {
+		Weight::from_parts(35_000_000, 20277)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn resolve_expired_market_invalid(o: u32, ) -> Weight {
+		Weight::from_parts(46_120_000, 24326)
+			.saturating_add(Weight::from_parts(14_305_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 3724).saturating_mul(o.into()))
+	}
+	/// The range of component `r` is `[1, 16]`.
+	fn resolve_expired_market_scalar(r: u32, ) -> Weight {
+		Weight::from_parts(38_772_000, 21530)
+			.saturating_add(Weight::from_parts(11_893_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// The range of component `i` is `[1, 33]`.
+	fn continue_destroy(i: u32, ) -> Weight {
+		Weight::from_parts(9_512_000, 6048)
+			.saturating_add(Weight::from_parts(2_134_000, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(i.into())))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(i.into())))
+	}
+	fn buy_outcome() -> Weight {
+		Weight::from_parts(64_000_000, 15304)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn report_ranking_as_oracle(o: u32, ) -> Weight {
+		Weight::from_parts(37_406_000, 20310)
+			.saturating_add(Weight::from_parts(118_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn accept_oracle_role() -> Weight {
+		Weight::from_parts(29_000_000, 7754)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn replace_oracle() -> Weight {
+		Weight::from_parts(21_000_000, 8216)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn cancel_market() -> Weight {
+		Weight::from_parts(48_000_000, 16940)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+	}
+	fn pause_market() -> Weight {
+		Weight::from_parts(19_000_000, 8216)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	fn unpause_market() -> Weight {
+		Weight::from_parts(33_000_000, 13674)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn close_market() -> Weight {
+		Weight::from_parts(96_000_000, 152830)
+			.saturating_add(RocksDbWeight::get().reads(73_u64))
+			.saturating_add(RocksDbWeight::get().writes(74_u64))
+	}
+	fn configure_scalar_market() -> Weight {
+		Weight::from_parts(24_000_000, 13274)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn report_scalar() -> Weight {
+		Weight::from_parts(33_000_000, 11508)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn sponsor_market() -> Weight {
+		Weight::from_parts(38_000_000, 11109)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn update_market_data() -> Weight {
+		Weight::from_parts(31_000_000, 16244)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn set_market_locale() -> Weight {
+		Weight::from_parts(24_000_000, 10731)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn force_refund_participant() -> Weight {
+		Weight::from_parts(214_000_000, 91320)
+			.saturating_add(RocksDbWeight::get().reads(36_u64))
+			.saturating_add(RocksDbWeight::get().writes(36_u64))
+	}
+	fn top_up_bond() -> Weight {
+		Weight::from_parts(27_000_000, 7659)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// The range of component `m` is `[1, 32]`.
+	fn require_bond_top_up(m: u32, ) -> Weight {
+		Weight::from_parts(7_812_000, 0)
+			.saturating_add(Weight::from_parts(21_406_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 10301).saturating_mul(m.into()))
+	}
+	fn retire_outcome() -> Weight {
+		Weight::from_parts(46_000_000, 18262)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn attest_resolution() -> Weight {
+		Weight::from_parts(22_000_000, 6431)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_max_bid() -> Weight {
+		Weight::from_parts(38_000_000, 14872)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn add_market_manager() -> Weight {
+		Weight::from_parts(19_000_000, 6096)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn remove_market_manager() -> Weight {
+		Weight::from_parts(19_000_000, 6096)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `c` is `[1, 50]`.
+	fn emit_highest_outcomes(c: u32, ) -> Weight {
+		Weight::from_parts(6_104_000, 3000)
+			.saturating_add(Weight::from_parts(14_912_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(c.into())))
+			.saturating_add(Weight::from_parts(0, 11102).saturating_mul(c.into()))
+	}
+	fn resolve_market_outcome() -> Weight {
+		Weight::from_parts(148_000_000, 58960)
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(24_u64))
+	}
+	/// The range of component `o` is `[1, 32]`.
+	fn resolve_market_invalid(o: u32, ) -> Weight {
+		Weight::from_parts(47_920_000, 24418)
+			.saturating_add(Weight::from_parts(14_190_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 3724).saturating_mul(o.into()))
+	}
+	fn dispute_market() -> Weight {
+		Weight::from_parts(15_000_000, 3630)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
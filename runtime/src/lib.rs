@@ -6,6 +6,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::{Decode, Encode, MaxEncodedLen};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -16,7 +17,7 @@ use sp_runtime::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	ApplyExtrinsicResult, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -27,7 +28,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, InstanceFilter, KeyOwnerProofSystem,
+		Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -35,15 +37,16 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -103,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 5,
 	state_version: 1,
 };
 
@@ -233,7 +236,7 @@ pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -267,9 +270,156 @@ impl pallet_sudo::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 }
 
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Allows all calls.
+	Any,
+	/// Allows the creator-only market calls, e.g. to manage markets with a hot key.
+	MarketCreator,
+	/// Allows to report market outcomes as oracle.
+	MarketOracle,
+	/// Allows to buy outcomes, to sponsor and to redeem markets.
+	MarketTrader,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, c: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::MarketCreator => matches!(
+				c,
+				RuntimeCall::TemplateModule(
+					pallet_template::Call::create_market { .. }
+						| pallet_template::Call::pause_market { .. }
+						| pallet_template::Call::unpause_market { .. }
+						| pallet_template::Call::clear_storage { .. }
+						| pallet_template::Call::replace_oracle { .. }
+						| pallet_template::Call::cancel_market { .. }
+						| pallet_template::Call::configure_scalar_market { .. }
+						| pallet_template::Call::update_market_data { .. }
+						| pallet_template::Call::set_market_locale { .. }
+						| pallet_template::Call::top_up_bond { .. }
+						| pallet_template::Call::retire_outcome { .. }
+						| pallet_template::Call::add_market_manager { .. }
+						| pallet_template::Call::remove_market_manager { .. }
+				)
+			),
+			ProxyType::MarketOracle => matches!(
+				c,
+				RuntimeCall::TemplateModule(
+					pallet_template::Call::report_as_oracle { .. }
+						| pallet_template::Call::report_ranking_as_oracle { .. }
+						| pallet_template::Call::accept_oracle_role { .. }
+						| pallet_template::Call::report_scalar { .. }
+						| pallet_template::Call::attest_resolution { .. }
+				)
+			),
+			ProxyType::MarketTrader => matches!(
+				c,
+				RuntimeCall::TemplateModule(
+					pallet_template::Call::buy_outcome { .. }
+						| pallet_template::Call::redeem { .. }
+						| pallet_template::Call::sponsor_market { .. }
+						| pallet_template::Call::set_max_bid { .. }
+				)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	pub const ProxyDepositBase: Balance = 40 * EXISTENTIAL_DEPOSIT;
+	pub const ProxyDepositFactor: Balance = 33 * EXISTENTIAL_DEPOSIT;
+	pub const AnnouncementDepositBase: Balance = 40 * EXISTENTIAL_DEPOSIT;
+	pub const AnnouncementDepositFactor: Balance = 66 * EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = ConstU32<32>;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = ConstU32<32>;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const MarketsPalletId: PalletId = PalletId(*b"py/markt");
+	pub const ScalarOutlierTolerance: Percent = Percent::from_percent(10);
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type BatchBidClearing = ConstBool<false>;
+	type BidIncrement = ConstU128<{ EXISTENTIAL_DEPOSIT }>;
+	type BidLogLength = ConstU32<32>;
+	type BondTopUpOrigin = EnsureRoot<AccountId>;
+	type Currency = Balances;
+	type CloseAnnouncementHorizon = ConstU32<{ 10 * MINUTES }>;
+	type CreationFee = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type CreationFeeExempt = frame_support::traits::Nothing;
+	type CreatorBond = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type DataUpdateDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type DestroyOrigin = EnsureRoot<AccountId>;
+	type FeeSchedule = pallet_template::DefaultFeeSchedule<ConstU128<{ 5 * EXISTENTIAL_DEPOSIT }>>;
+	type InformationalEvents = ConstBool<true>;
+	type MarketCreatorClearStorageTime = ConstU32<{ 7 * DAYS }>;
+	type MaxAttestationLength = ConstU32<256>;
+	type MaxBidsPerBlock = ConstU32<4>;
+	type MaxDataUpdates = ConstU32<3>;
+	type MaxManagers = ConstU32<8>;
+	type MaxMarketsPerBlock = ConstU32<16>;
+	type MaxOutcomes = ConstU32<32>;
+	type MaxPauseDuration = ConstU32<{ 7 * DAYS }>;
+	type MaxQueuedBids = ConstU32<256>;
+	type MaxScalarOracles = ConstU32<16>;
+	type MaxSponsors = ConstU32<16>;
+	type MinMarketPeriod = ConstU32<{ 10 * MINUTES }>;
+	type ModerationOrigin = EnsureRoot<AccountId>;
+	// The creation fee is burned.
+	type OnCreationFee = ();
+	// Engagement programs are appended to this tuple.
+	type OnParticipation = ();
+	type OracleAcceptancePeriod = ConstU32<{ 2 * HOURS }>;
+	type OracleBond = ConstU128<{ 50 * EXISTENTIAL_DEPOSIT }>;
+	type OracleGracePeriod = ConstU32<{ HOURS }>;
+	type PalletId = MarketsPalletId;
+	type ReportingPeriod = ConstU32<{ DAYS }>;
+	type ScalarOutlierTolerance = ScalarOutlierTolerance;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
@@ -290,6 +440,7 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		Proxy: pallet_proxy,
 	}
 );
 
@@ -323,8 +474,18 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// All migrations of the runtime, which are executed on the next runtime upgrade.
+pub type Migrations = (
+	pallet_template::migrations::v1::MigrateMarketAccounts<Runtime>,
+	pallet_template::migrations::v2::MigrateBidsToHolds<Runtime>,
+	pallet_template::migrations::v3::MigrateCloseSchedule<Runtime>,
+	pallet_template::migrations::v4::BackfillAccountPositions<Runtime>,
+	pallet_template::migrations::v5::InitializeMarketCounters<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -335,6 +496,7 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_proxy, Proxy]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
 	);
@@ -508,6 +670,67 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_template_runtime_api::MarketsApi<Block, AccountId, Balance, BlockNumber, pallet_template::MarketId> for Runtime {
+		fn market_account(market_id: pallet_template::MarketId) -> AccountId {
+			TemplateModule::market_account(market_id)
+		}
+
+		fn markets_closing_in(
+			from: BlockNumber,
+			to: BlockNumber,
+		) -> Vec<(BlockNumber, Vec<pallet_template::MarketId>)> {
+			TemplateModule::markets_closing_in(from, to)
+		}
+
+		fn validate_market_params(
+			params: pallet_template::MarketParams<AccountId, BlockNumber>,
+		) -> Result<pallet_template::MarketId, pallet_template::CreateMarketError> {
+			TemplateModule::validate_market_params(params)
+		}
+
+		fn settlement_report(
+			market_id: pallet_template::MarketId,
+		) -> Option<Vec<pallet_template::SettlementEntry<AccountId, Balance>>> {
+			TemplateModule::settlement_report(market_id)
+		}
+
+		fn market_constants() -> pallet_template::MarketConstants<Balance, BlockNumber> {
+			TemplateModule::market_constants()
+		}
+
+		fn account_positions(account: AccountId) -> Vec<pallet_template::PositionInfo<Balance>> {
+			TemplateModule::account_positions(account)
+		}
+
+		fn twap(
+			market_id: pallet_template::MarketId,
+			outcome_index: u8,
+			window: BlockNumber,
+		) -> Option<Perbill> {
+			TemplateModule::twap(market_id, outcome_index, window)
+		}
+
+		fn markets_in_region(
+			region: [u8; 2],
+			language: Option<[u8; 2]>,
+		) -> Vec<pallet_template::MarketId> {
+			TemplateModule::markets_in_region(region, language)
+		}
+
+		fn quote_buy(
+			market_id: pallet_template::MarketId,
+			outcome_index: u8,
+			buyer: AccountId,
+			price: Balance,
+		) -> Result<pallet_template::BuyQuote<AccountId, Balance>, pallet_template::BidError> {
+			TemplateModule::quote_buy(market_id, outcome_index, &buyer, price)
+		}
+
+		fn market_metrics() -> pallet_template::MarketMetrics<Balance> {
+			TemplateModule::market_metrics()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (